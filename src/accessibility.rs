@@ -0,0 +1,214 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Describing the contents of windows to assistive technologies.
+//!
+//! Since `gui-tools` does not draw any widgets itself, screen readers
+//! have no way of knowing what a window contains. Applications describe
+//! it instead, as a tree of `AccessNode`s attached to each window with
+//! `Runtime::window_set_access_tree`. Backends expose the tree through
+//! AT-SPI2 on Linux and UI Automation on Windows, and report requests
+//! from assistive technologies as `EventType::AccessAction`.
+
+use crate::{async_runtime::Runtime, Rectangle, Result};
+use alloc::{string::String, vec::Vec};
+use core::ops::{BitOr, BitOrAssign};
+
+/// A node in a tree describing the contents of a window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessNode {
+    /// The identifier reported when an action is performed on this
+    /// node.
+    pub id: AccessNodeId,
+    /// What kind of element this node is.
+    pub role: Role,
+    /// The name read out for this node, e.g. a button's label.
+    pub name: String,
+    /// A longer description of this node, if any.
+    pub description: Option<String>,
+    /// The area this node covers, relative to the window.
+    pub bounds: Rectangle,
+    /// The current state of this node.
+    pub states: AccessStates,
+    /// The children of this node.
+    pub children: Vec<AccessNode>,
+}
+
+/// The identifier for a node in an accessibility tree.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AccessNodeId(u32);
+
+impl AccessNodeId {
+    /// Create a new node identifier.
+    pub fn new(id: u32) -> Self {
+        AccessNodeId(id)
+    }
+
+    /// Get the inner identifier.
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+/// The kind of element that a node represents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Role {
+    /// A window, or the root of a tree.
+    Window,
+    /// A container grouping other nodes.
+    Group,
+    /// A push button.
+    Button,
+    /// A check box.
+    CheckBox,
+    /// A radio button.
+    RadioButton,
+    /// Static text.
+    Label,
+    /// An editable text field.
+    TextInput,
+    /// An image.
+    Image,
+    /// A list of items.
+    List,
+    /// An item in a list.
+    ListItem,
+    /// A menu.
+    Menu,
+    /// An item in a menu.
+    MenuItem,
+    /// A slider that selects a value from a range.
+    Slider,
+    /// A progress bar.
+    ProgressBar,
+    /// A hyperlink.
+    Link,
+}
+
+/// A set of states that a node can be in.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AccessStates(u16);
+
+impl AccessStates {
+    /// No states.
+    pub const NONE: AccessStates = AccessStates(0);
+    /// The node has the keyboard focus.
+    pub const FOCUSED: AccessStates = AccessStates(1 << 0);
+    /// The node can receive the keyboard focus.
+    pub const FOCUSABLE: AccessStates = AccessStates(1 << 1);
+    /// The node is checked, e.g. a ticked check box.
+    pub const CHECKED: AccessStates = AccessStates(1 << 2);
+    /// The node is selected, e.g. an item in a list.
+    pub const SELECTED: AccessStates = AccessStates(1 << 3);
+    /// The node is expanded, e.g. an open submenu.
+    pub const EXPANDED: AccessStates = AccessStates(1 << 4);
+    /// The node cannot currently be interacted with.
+    pub const DISABLED: AccessStates = AccessStates(1 << 5);
+    /// The node is not displayed.
+    pub const HIDDEN: AccessStates = AccessStates(1 << 6);
+
+    /// Tell whether all of the states in `other` are set in this one.
+    pub fn contains(self, other: AccessStates) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Tell whether no states are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for AccessStates {
+    type Output = AccessStates;
+
+    fn bitor(self, rhs: AccessStates) -> AccessStates {
+        AccessStates(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for AccessStates {
+    fn bitor_assign(&mut self, rhs: AccessStates) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// An action that an assistive technology asked to perform on a node.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum AccessAction {
+    /// Activate the node, e.g. press a button.
+    Activate,
+    /// Give the node the keyboard focus.
+    Focus,
+    /// Scroll the node into view.
+    ScrollIntoView,
+}
+
+/// How urgently an announcement should be read out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Read out the announcement once the screen reader is idle.
+    Polite,
+    /// Interrupt whatever the screen reader is reading.
+    Assertive,
+}
+
+/// Ask screen readers to read out `text`, even though it is not tied to
+/// any focusable node, e.g. to report that a download has finished.
+///
+/// This is sent as an AT-SPI announcement event on Linux and a UI
+/// Automation notification on Windows.
+pub async fn announce<R: Runtime + ?Sized>(
+    runtime: &mut R,
+    text: &str,
+    priority: Priority,
+) -> Result {
+    runtime.access_announce(text, priority).await
+}
+
+impl AccessNode {
+    /// Create a new node with no description, states or children.
+    pub fn new(id: AccessNodeId, role: Role, name: impl Into<String>, bounds: Rectangle) -> Self {
+        AccessNode {
+            id,
+            role,
+            name: name.into(),
+            description: None,
+            bounds,
+            states: AccessStates::NONE,
+            children: Vec::new(),
+        }
+    }
+
+    /// Add a child to the end of this node's children.
+    pub fn child(&mut self, child: AccessNode) -> &mut Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Find the node with the given identifier in this tree.
+    pub fn find(&self, id: AccessNodeId) -> Option<&AccessNode> {
+        if self.id == id {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.find(id))
+    }
+}
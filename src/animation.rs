@@ -0,0 +1,430 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Tweens, easing curves and springs, driven by redraw events.
+//!
+//! An `Animator` holds the animations running in each window. Advance it
+//! with `Animator::frame` whenever `EventType::RedrawRequested` is
+//! delivered; it requests another redraw for every window that still has
+//! an animation running, so animations stay in step with the monitor's
+//! refresh and stop costing anything once they finish.
+
+use crate::{async_runtime::Runtime, Result, Window};
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::time::Duration;
+
+/// A curve mapping the progress of an animation, from 0 to 1, onto the
+/// progress of its value.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Easing {
+    /// Progress at a constant rate.
+    Linear,
+    /// Start slowly and accelerate, quadratically.
+    EaseInQuad,
+    /// Start quickly and decelerate, quadratically.
+    EaseOutQuad,
+    /// Accelerate and then decelerate, quadratically.
+    EaseInOutQuad,
+    /// Start slowly and accelerate, cubically.
+    EaseInCubic,
+    /// Start quickly and decelerate, cubically.
+    EaseOutCubic,
+    /// Accelerate and then decelerate, cubically.
+    #[default]
+    EaseInOutCubic,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` with the control
+    /// points `(x1, y1)` and `(x2, y2)`, as in CSS's `cubic-bezier()`.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// The curve CSS calls `ease`.
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+
+    /// Apply the curve to `t`, which is clamped to between 0 and 1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInQuad => t * t,
+            Easing::EaseOutQuad => t * (2.0 - t),
+            Easing::EaseInOutQuad => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - u * u / 2.0
+                }
+            }
+            Easing::EaseInCubic => t * t * t,
+            Easing::EaseOutCubic => {
+                let u = 1.0 - t;
+                1.0 - u * u * u
+            }
+            Easing::EaseInOutCubic => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let u = 2.0 - 2.0 * t;
+                    1.0 - u * u * u / 2.0
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                let s = solve_bezier(t, x1, x2);
+                bezier(s, y1, y2)
+            }
+        }
+    }
+}
+
+/// Evaluate one dimension of a cubic Bézier curve from 0 to 1.
+fn bezier(s: f32, p1: f32, p2: f32) -> f32 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * p1 + 3.0 * u * s * s * p2 + s * s * s
+}
+
+/// Find the parameter at which the curve's X coordinate is `x`.
+///
+/// The X coordinate increases monotonically for control points between
+/// 0 and 1, so bisection always converges.
+fn solve_bezier(x: f32, x1: f32, x2: f32) -> f32 {
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut s = x;
+    for _ in 0..32 {
+        let guess = bezier(s, x1, x2);
+        if abs(guess - x) < 1e-6 {
+            break;
+        }
+
+        if guess < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    s
+}
+
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Moves a value from one number to another over a fixed duration.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+impl Tween {
+    /// Create a new `Tween` from `from` to `to`, over `duration`.
+    pub fn new(from: f32, to: f32, duration: Duration) -> Self {
+        Tween {
+            from,
+            to,
+            duration,
+            elapsed: Duration::from_secs(0),
+            easing: Easing::default(),
+        }
+    }
+
+    /// Use the given easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Get the current value.
+    pub fn value(&self) -> f32 {
+        let t = if self.duration == Duration::from_secs(0) {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    /// Advance the tween by `elapsed`.
+    pub fn advance(&mut self, elapsed: Duration) {
+        self.elapsed = self.elapsed.saturating_add(elapsed).min(self.duration);
+    }
+
+    /// Tell whether the tween has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Moves a value towards a target as though it were attached to it by a
+/// damped spring.
+///
+/// Unlike a `Tween`, the target can be changed while the spring is
+/// moving, and the value carries its velocity over smoothly.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spring {
+    value: f32,
+    velocity: f32,
+    target: f32,
+    stiffness: f32,
+    damping: f32,
+    mass: f32,
+}
+
+impl Spring {
+    /// The largest step the simulation takes at once, to keep it stable
+    /// through long frames.
+    const STEP: Duration = Duration::from_nanos(1_000_000_000 / 240);
+    /// The longest time the simulation catches up on at once. Longer
+    /// gaps, e.g. after the system was suspended, count as this long.
+    const MAX_FRAME: Duration = Duration::from_millis(100);
+    /// How close to the target, and how slow, the value needs to be for
+    /// the spring to come to rest.
+    const REST: f32 = 1e-3;
+
+    /// Create a new `Spring` at rest at `value`.
+    ///
+    /// The default spring is critically damped, so it reaches its target
+    /// quickly without overshooting.
+    pub fn new(value: f32) -> Self {
+        Spring {
+            value,
+            velocity: 0.0,
+            target: value,
+            stiffness: 170.0,
+            damping: 26.0,
+            mass: 1.0,
+        }
+    }
+
+    /// Use the given stiffness, damping and mass.
+    pub fn with_parameters(mut self, stiffness: f32, damping: f32, mass: f32) -> Self {
+        self.stiffness = stiffness;
+        self.damping = damping;
+        self.mass = mass;
+        self
+    }
+
+    /// Get the current value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Get the current velocity, in units per second.
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// Get the value the spring is moving towards.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Set the value the spring is moving towards.
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+    }
+
+    /// Advance the spring by `elapsed`, or by 100 milliseconds if that is
+    /// longer.
+    pub fn advance(&mut self, elapsed: Duration) {
+        let elapsed = elapsed.min(Self::MAX_FRAME);
+        let step = Self::STEP.as_nanos();
+        let steps = elapsed.as_nanos().div_ceil(step);
+        let dt = elapsed.as_secs_f32() / steps as f32;
+
+        for _ in 0..steps {
+            if self.is_settled() {
+                break;
+            }
+
+            let force = -self.stiffness * (self.value - self.target) - self.damping * self.velocity;
+            self.velocity += force / self.mass * dt;
+            self.value += self.velocity * dt;
+        }
+
+        if self.is_settled() {
+            self.value = self.target;
+            self.velocity = 0.0;
+        }
+    }
+
+    /// Tell whether the spring has come to rest at its target.
+    pub fn is_settled(&self) -> bool {
+        abs(self.value - self.target) < Self::REST && abs(self.velocity) < Self::REST
+    }
+}
+
+/// The ID of an animation in an `Animator`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnimationId(u64);
+
+#[derive(Debug, Clone)]
+enum Animation {
+    Tween(Tween),
+    Spring(Spring),
+}
+
+impl Animation {
+    fn value(&self) -> f32 {
+        match self {
+            Animation::Tween(tween) => tween.value(),
+            Animation::Spring(spring) => spring.value(),
+        }
+    }
+
+    fn advance(&mut self, elapsed: Duration) {
+        match self {
+            Animation::Tween(tween) => tween.advance(elapsed),
+            Animation::Spring(spring) => spring.advance(elapsed),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        match self {
+            Animation::Tween(tween) => tween.is_finished(),
+            Animation::Spring(spring) => spring.is_settled(),
+        }
+    }
+}
+
+/// Keeps track of the animations running in each window.
+///
+/// Finished animations keep their final value until they are removed, so
+/// that the last frame can still read it.
+#[derive(Debug, Clone, Default)]
+pub struct Animator {
+    animations: BTreeMap<AnimationId, (Window, Animation)>,
+    next_id: u64,
+}
+
+impl Animator {
+    /// Create a new, empty `Animator`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, window: Window, animation: Animation) -> AnimationId {
+        let id = AnimationId(self.next_id);
+        self.next_id += 1;
+        self.animations.insert(id, (window, animation));
+        id
+    }
+
+    /// Start a tween that animates part of `window`.
+    pub fn add_tween(&mut self, window: Window, tween: Tween) -> AnimationId {
+        self.insert(window, Animation::Tween(tween))
+    }
+
+    /// Start a spring that animates part of `window`.
+    pub fn add_spring(&mut self, window: Window, spring: Spring) -> AnimationId {
+        self.insert(window, Animation::Spring(spring))
+    }
+
+    /// Get the current value of an animation.
+    pub fn value(&self, id: AnimationId) -> Option<f32> {
+        self.animations
+            .get(&id)
+            .map(|(_, animation)| animation.value())
+    }
+
+    /// Move a spring towards a new target, setting it moving again if it
+    /// had come to rest.
+    ///
+    /// Returns `false` if `id` is not a spring.
+    pub fn set_spring_target(&mut self, id: AnimationId, target: f32) -> bool {
+        match self.animations.get_mut(&id) {
+            Some((_, Animation::Spring(spring))) => {
+                spring.set_target(target);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Tell whether an animation has finished.
+    ///
+    /// Animations that do not exist count as finished.
+    pub fn is_finished(&self, id: AnimationId) -> bool {
+        match self.animations.get(&id) {
+            Some((_, animation)) => animation.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Remove an animation.
+    pub fn remove(&mut self, id: AnimationId) {
+        self.animations.remove(&id);
+    }
+
+    /// Remove every animation in a window, e.g. once it is destroyed.
+    pub fn remove_window(&mut self, window: Window) {
+        self.animations.retain(|_, (w, _)| *w != window);
+    }
+
+    /// Remove every animation that has finished.
+    pub fn remove_finished(&mut self) {
+        self.animations
+            .retain(|_, (_, animation)| !animation.is_finished());
+    }
+
+    /// Get the windows that have an animation running.
+    pub fn active_windows(&self) -> BTreeSet<Window> {
+        self.animations
+            .values()
+            .filter(|(_, animation)| !animation.is_finished())
+            .map(|(window, _)| *window)
+            .collect()
+    }
+
+    /// Tell whether any animation is running.
+    pub fn is_active(&self) -> bool {
+        self.animations
+            .values()
+            .any(|(_, animation)| !animation.is_finished())
+    }
+
+    /// Advance every animation by `elapsed`.
+    pub fn advance(&mut self, elapsed: Duration) {
+        for (_, animation) in self.animations.values_mut() {
+            animation.advance(elapsed);
+        }
+    }
+
+    /// Advance every animation by `elapsed`, the time since the previous
+    /// frame, and request a redraw of every window that still has an
+    /// animation running.
+    pub async fn frame<R: Runtime + ?Sized>(
+        &mut self,
+        runtime: &mut R,
+        elapsed: Duration,
+    ) -> Result {
+        self.advance(elapsed);
+        for window in self.active_windows() {
+            runtime.window_request_redraw(window).await?;
+        }
+        Ok(())
+    }
+}
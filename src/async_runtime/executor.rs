@@ -0,0 +1,284 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use alloc::{boxed::Box, sync::Arc, task::Wake, vec::Vec};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+use spin::Mutex;
+
+/// A future spawned onto a runtime with `Runtime::spawn_task`.
+pub type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Wrap a future into a `Task`, along with a `JoinHandle` that resolves
+/// to its output once it completes.
+///
+/// `RuntimeExt::spawn` does this for you.
+pub fn join_handle<F>(future: F) -> (Task, JoinHandle<F::Output>)
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (completion, handle) = completion();
+    let task = Box::pin(async move { completion.complete(future.await) });
+    (task, handle)
+}
+
+/// Create a `JoinHandle` that resolves once the `Completion` is given a
+/// value.
+pub(crate) fn completion<T>() -> (Completion<T>, JoinHandle<T>) {
+    let state = Arc::new(Mutex::new(JoinState {
+        result: None,
+        waker: None,
+    }));
+    let handle = JoinHandle {
+        state: state.clone(),
+    };
+    let completion = Completion {
+        state,
+        abandoned: None,
+    };
+    (completion, handle)
+}
+
+/// Create a `JoinHandle` that resolves once the `Completion` is given a
+/// value, or to `abandoned()` if the `Completion` is dropped first.
+pub(crate) fn completion_or<T>(abandoned: fn() -> T) -> (Completion<T>, JoinHandle<T>) {
+    let (mut completion, handle) = completion();
+    completion.abandoned = Some(abandoned);
+    (completion, handle)
+}
+
+/// The sending half of a `JoinHandle`.
+pub(crate) struct Completion<T> {
+    state: Arc<Mutex<JoinState<T>>>,
+    /// Produces the output if the completion is dropped without one.
+    abandoned: Option<fn() -> T>,
+}
+
+impl<T> Completion<T> {
+    /// Store the output, and wake whoever is waiting on the handle.
+    pub(crate) fn complete(mut self, result: T) {
+        self.abandoned = None;
+        self.resolve(result);
+    }
+
+    fn resolve(&self, result: T) {
+        let waker = {
+            let mut state = self.state.lock();
+            state.result = Some(result);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completion<T> {
+    fn drop(&mut self) {
+        if let Some(abandoned) = self.abandoned.take() {
+            self.resolve(abandoned());
+        }
+    }
+}
+
+/// A future that resolves to the output of a spawned task.
+///
+/// Dropping the handle does not cancel the task.
+pub struct JoinHandle<T> {
+    state: Arc<Mutex<JoinState<T>>>,
+}
+
+struct JoinState<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+impl<T> JoinHandle<T> {
+    /// Tell whether the task has finished and its output is waiting to
+    /// be taken.
+    pub fn is_finished(&self) -> bool {
+        self.state.lock().result.is_some()
+    }
+
+    /// Take the output of the task, if it has finished.
+    pub fn try_take(&mut self) -> Option<T> {
+        self.state.lock().result.take()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A small executor for the tasks spawned onto a runtime.
+///
+/// This is intended for use by backends that are not running on top of
+/// another async runtime. The event loop waits on `Executor::wait`
+/// alongside its events, and calls `Executor::run_ready` whenever it
+/// resolves, so tasks run on the GUI thread and their completions wake
+/// the event loop.
+#[derive(Default)]
+pub struct Executor {
+    tasks: Vec<(Task, Arc<TaskWaker>)>,
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    /// Whether any task has been woken since the last run.
+    woken: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+struct TaskWaker {
+    woken: AtomicBool,
+    shared: Arc<Shared>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+        self.shared.woken.store(true, Ordering::Release);
+
+        if let Some(waker) = self.shared.waker.lock().take() {
+            waker.wake();
+        }
+    }
+}
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Executor")
+            .field("tasks", &self.tasks.len())
+            .field("woken", &self.has_ready())
+            .finish()
+    }
+}
+
+impl Executor {
+    /// Create a new `Executor` with no tasks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a task, which is first polled on the next `run_ready`.
+    pub fn spawn(&mut self, task: Task) {
+        let waker = Arc::new(TaskWaker {
+            woken: AtomicBool::new(true),
+            shared: self.shared.clone(),
+        });
+        self.shared.woken.store(true, Ordering::Release);
+        self.tasks.push((task, waker));
+    }
+
+    /// Get the number of unfinished tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Tell whether every task has finished.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Tell whether any task is ready to be polled.
+    pub fn has_ready(&self) -> bool {
+        self.shared.woken.load(Ordering::Acquire)
+    }
+
+    /// Drop every task.
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+        self.shared.woken.store(false, Ordering::Release);
+    }
+
+    /// Poll every task that has been woken, returning the number of tasks
+    /// that were polled.
+    pub fn run_ready(&mut self) -> usize {
+        self.shared.woken.store(false, Ordering::Release);
+        let mut polled = 0;
+
+        self.tasks.retain_mut(|(task, waker)| {
+            if !waker.woken.swap(false, Ordering::AcqRel) {
+                return true;
+            }
+
+            polled += 1;
+            let std_waker = Waker::from(waker.clone());
+            let mut cx = Context::from_waker(&std_waker);
+            task.as_mut().poll(&mut cx).is_pending()
+        });
+
+        polled
+    }
+
+    /// Wait until at least one task is ready to be polled.
+    pub fn wait(&self) -> impl Future<Output = ()> + Send + '_ {
+        Wait {
+            shared: &self.shared,
+        }
+    }
+}
+
+struct Wait<'a> {
+    shared: &'a Shared,
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // register the waker first, so that a task woken in between the
+        // check and the registration isn't missed
+        *self.shared.waker.lock() = Some(cx.waker().clone());
+
+        if self.shared.woken.load(Ordering::Acquire) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -26,4 +26,11 @@ pub enum GeneralRuntime {
     Dynamic(Box<dyn Runtime + Send + 'static>),
 }
 
-impl Runtime for GeneralRuntime {}
+impl From<Box<dyn Runtime + Send + 'static>> for GeneralRuntime {
+    fn from(runtime: Box<dyn Runtime + Send + 'static>) -> Self {
+        GeneralRuntime::Dynamic(runtime)
+    }
+}
+
+// the `Runtime` implementation for `GeneralRuntime` is generated
+// alongside the trait, in runtime.rs
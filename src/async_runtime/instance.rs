@@ -0,0 +1,333 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::RuntimeProxy;
+use crate::{Event, EventType, Result};
+use alloc::{string::String, vec::Vec};
+
+/// The outcome of `single_instance`.
+#[derive(Debug)]
+pub enum SingleInstance {
+    /// No other instance is running, so this process is the primary
+    /// instance. Messages from later instances arrive through the
+    /// listener.
+    Primary(InstanceListener),
+    /// Another instance is already running. Arguments can be forwarded
+    /// to it through the sender, after which this process should exit.
+    Secondary(InstanceSender),
+}
+
+/// Receives messages from later instances of the application.
+#[derive(Debug)]
+pub struct InstanceListener {
+    inner: imp::Listener,
+}
+
+/// Sends a message to the primary instance of the application.
+#[derive(Debug)]
+pub struct InstanceSender {
+    inner: imp::Sender,
+}
+
+/// Check whether another instance of the application identified by
+/// `app_id` is already running.
+///
+/// On Unix-likes, this is done with a socket named after `app_id` in
+/// `XDG_RUNTIME_DIR`, or in a private directory in the temporary
+/// directory if that is not set. Either directory must be accessible
+/// only to the user, so other users can neither take the socket nor
+/// send messages to it. Other platforms are not supported yet.
+pub fn single_instance(app_id: &str) -> Result<SingleInstance> {
+    imp::single_instance(app_id)
+}
+
+impl InstanceListener {
+    /// Wait for a later instance to send a message, returning the
+    /// arguments it sent.
+    pub fn accept(&self) -> Result<Vec<String>> {
+        self.inner.accept()?.read()
+    }
+
+    /// Spawn a thread that receives messages from later instances and
+    /// injects them into the runtime as `EventType::InstanceMessage`
+    /// events.
+    ///
+    /// A message that cannot be received is logged and skipped; the
+    /// thread only stops if the listener itself fails.
+    pub fn forward_to(self, proxy: RuntimeProxy) -> std::thread::JoinHandle<()> {
+        std::thread::spawn(move || loop {
+            let connection = match self.inner.accept() {
+                Ok(connection) => connection,
+                Err(err) => {
+                    tracing::error!("Failed to listen for other instances: {}", &err);
+                    return;
+                }
+            };
+
+            match connection.read() {
+                Ok(args) => {
+                    let event = Event::new(None, EventType::InstanceMessage(args));
+                    proxy.send(move |rt| rt.inject_event(event));
+                }
+                Err(err) => {
+                    tracing::warn!("Failed to receive message from instance: {}", &err);
+                }
+            }
+        })
+    }
+}
+
+impl InstanceSender {
+    /// Send arguments, typically the command line, to the primary
+    /// instance.
+    pub fn send(self, args: &[String]) -> Result {
+        self.inner.send(args)
+    }
+}
+
+/// Encode a message as a list of NUL-terminated arguments.
+fn encode(args: &[String]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for arg in args {
+        buf.extend_from_slice(arg.as_bytes());
+        buf.push(0);
+    }
+    buf
+}
+
+fn decode(buf: &[u8]) -> Vec<String> {
+    String::from_utf8_lossy(buf)
+        .split_terminator('\0')
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{decode, encode, InstanceListener, InstanceSender, SingleInstance};
+    use crate::{Error, Result};
+    use alloc::{format, string::String, vec::Vec};
+    use core::time::Duration;
+    use std::{
+        io::{ErrorKind, Read, Write},
+        net::Shutdown,
+        os::unix::net::{SocketAddr, UnixListener, UnixStream},
+        path::PathBuf,
+        time::Instant,
+    };
+
+    /// How many times to try to become either the primary or a secondary
+    /// instance, when racing against other instances starting up.
+    const ATTEMPTS: usize = 3;
+
+    /// How long a later instance may take to send its whole message.
+    const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// The largest message a later instance may send.
+    const MAX_MESSAGE: usize = 1 << 20;
+
+    #[derive(Debug)]
+    pub(super) struct Listener {
+        listener: UnixListener,
+        path: PathBuf,
+    }
+
+    #[derive(Debug)]
+    pub(super) struct Connection {
+        stream: UnixStream,
+    }
+
+    #[derive(Debug)]
+    pub(super) struct Sender {
+        stream: UnixStream,
+    }
+
+    pub(super) fn single_instance(app_id: &str) -> Result<SingleInstance> {
+        let name: String = app_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let path = runtime_dir()?.join(format!("gui-tools-{}.sock", name));
+        let addr = SocketAddr::from_pathname(&path)?;
+
+        for _ in 0..ATTEMPTS {
+            match UnixStream::connect_addr(&addr) {
+                Ok(stream) => {
+                    tracing::debug!("Found running instance at {:?}", &addr);
+                    return Ok(SingleInstance::Secondary(InstanceSender {
+                        inner: Sender { stream },
+                    }));
+                }
+                Err(ref err) if err.kind() == ErrorKind::ConnectionRefused => {
+                    // the socket was left behind by an instance that
+                    // crashed
+                    match std::fs::remove_file(&path) {
+                        Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                        _ => {}
+                    }
+                }
+                Err(ref err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            match UnixListener::bind_addr(&addr) {
+                Ok(listener) => {
+                    return Ok(SingleInstance::Primary(InstanceListener {
+                        inner: Listener {
+                            listener,
+                            path: path.clone(),
+                        },
+                    }))
+                }
+                // another instance started at the same time, and got
+                // there first
+                Err(ref err) if err.kind() == ErrorKind::AddrInUse => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        Err(Error::conflict("The single-instance socket"))
+    }
+
+    /// Get a directory that only this user can access.
+    ///
+    /// The socket's file permissions are not enough to keep other users
+    /// out, since they could bind the name before this user does, so the
+    /// directory holding it must be private.
+    fn runtime_dir() -> Result<PathBuf> {
+        use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+        let dir = match std::env::var_os("XDG_RUNTIME_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => {
+                let user = std::env::var("USER").unwrap_or_else(|_| String::from("unknown"));
+                let dir = std::env::temp_dir().join(format!("gui-tools-{}", user));
+                match std::fs::DirBuilder::new().mode(0o700).create(&dir) {
+                    Err(err) if err.kind() != ErrorKind::AlreadyExists => return Err(err.into()),
+                    _ => {}
+                }
+                dir
+            }
+        };
+
+        // a directory made by someone else can't be trusted; if it is
+        // private to another user, binding in it fails anyway
+        let metadata = std::fs::symlink_metadata(&dir)?;
+        if !metadata.is_dir() || metadata.permissions().mode() & 0o077 != 0 {
+            return Err(Error::static_msg(
+                "The single-instance socket directory is not private",
+            ));
+        }
+
+        Ok(dir)
+    }
+
+    impl Listener {
+        pub(super) fn accept(&self) -> Result<Connection> {
+            let (stream, _) = self.listener.accept()?;
+            Ok(Connection { stream })
+        }
+    }
+
+    impl Connection {
+        pub(super) fn read(mut self) -> Result<Vec<String>> {
+            let deadline = Instant::now() + READ_TIMEOUT;
+            let mut buf = Vec::new();
+            let mut chunk = [0; 4096];
+
+            loop {
+                // the timeout applies to each read, so shrink it to what
+                // is left of the overall deadline
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining == Duration::from_secs(0) {
+                    return Err(Error::static_msg(
+                        "Timed out waiting for the instance's message",
+                    ));
+                }
+                self.stream.set_read_timeout(Some(remaining))?;
+
+                match self.stream.read(&mut chunk) {
+                    Ok(0) => return Ok(decode(&buf)),
+                    Ok(n) if buf.len() + n > MAX_MESSAGE => {
+                        return Err(Error::static_msg("The instance's message is too large"));
+                    }
+                    Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    Err(ref err) if err.kind() == ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    impl Sender {
+        pub(super) fn send(mut self, args: &[String]) -> Result {
+            self.stream.write_all(&encode(args))?;
+            self.stream.shutdown(Shutdown::Write)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use super::SingleInstance;
+    use crate::{Error, Result};
+    use alloc::{string::String, vec::Vec};
+
+    #[derive(Debug)]
+    pub(super) enum Listener {}
+
+    #[derive(Debug)]
+    pub(super) enum Connection {}
+
+    #[derive(Debug)]
+    pub(super) enum Sender {}
+
+    pub(super) fn single_instance(_app_id: &str) -> Result<SingleInstance> {
+        Err(Error::unsupported("single_instance"))
+    }
+
+    impl Listener {
+        pub(super) fn accept(&self) -> Result<Connection> {
+            match *self {}
+        }
+    }
+
+    impl Connection {
+        pub(super) fn read(self) -> Result<Vec<String>> {
+            match self {}
+        }
+    }
+
+    impl Sender {
+        pub(super) fn send(self, _args: &[String]) -> Result {
+            match self {}
+        }
+    }
+}
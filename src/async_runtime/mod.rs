@@ -15,13 +15,30 @@
 // Public License along with gui-tools. If not, see
 // <https://www.gnu.org/licenses/>.
 
+mod executor;
+pub use executor::{join_handle, Executor, JoinHandle, Task};
+
 mod general;
 pub use general::GeneralRuntime;
 
+mod proxy;
+pub use proxy::{Command, ProxyQueue, RuntimeProxy};
+
+mod shutdown;
+pub use shutdown::ShutdownReport;
+
 mod runtime;
-pub use runtime::Runtime;
+pub use runtime::{ready, Runtime, RuntimeExt, RuntimeFuture};
+
+#[cfg(feature = "trace-spans")]
+mod traced;
+#[cfg(feature = "trace-spans")]
+pub use traced::TracedRuntime;
 
 cfg_std! {
+    mod instance;
+    pub use instance::{InstanceListener, InstanceSender, SingleInstance, single_instance};
+
     mod selector;
-    pub use selector::{Backend, BackendInfo, RuntimeBuilder, add_backend};
+    pub use selector::{Backend, BackendInfo, Producer, RuntimeBuilder, add_backend, select_runtime, BACKEND_ENV_VAR};
 }
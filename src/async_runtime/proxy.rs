@@ -0,0 +1,266 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{executor::completion_or, JoinHandle, Runtime, RuntimeFuture};
+use crate::{Error, Rectangle, Result, Visibility, Window};
+use alloc::{boxed::Box, collections::VecDeque, string::String, sync::Arc};
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll, Waker},
+};
+use spin::Mutex;
+
+/// A command sent through a `RuntimeProxy`, to be run on the thread that
+/// owns the runtime.
+pub type Command = Box<dyn for<'a> FnOnce(&'a mut dyn Runtime) -> RuntimeFuture<'a> + Send>;
+
+/// The receiving end of a set of `RuntimeProxy`s.
+///
+/// This lives on the thread that owns the runtime, which should wait on
+/// `ProxyQueue::wait` alongside its events and call
+/// `ProxyQueue::run_pending` whenever it resolves. Once it is dropped,
+/// commands that have not run yet, and any sent later, are discarded.
+#[derive(Debug, Default)]
+pub struct ProxyQueue {
+    shared: Arc<Shared>,
+}
+
+/// A handle that can be sent to other threads to request operations on
+/// a runtime.
+///
+/// Runtimes are generally not thread safe, so operations requested
+/// through a proxy are queued and then run on the runtime's own thread.
+/// Errors from these operations are logged, since there is no caller to
+/// report them to.
+#[derive(Debug, Clone)]
+pub struct RuntimeProxy {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    commands: Mutex<VecDeque<Command>>,
+    waker: Mutex<Option<Waker>>,
+    /// Whether the `ProxyQueue` has been dropped.
+    ///
+    /// This is only changed with `commands` locked, so that no command
+    /// can be queued after the queue is drained.
+    closed: AtomicBool,
+}
+
+impl fmt::Debug for Shared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shared")
+            .field("pending", &self.commands.lock().len())
+            .finish()
+    }
+}
+
+impl ProxyQueue {
+    /// Create a new, empty `ProxyQueue`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a proxy that sends commands to this queue.
+    pub fn proxy(&self) -> RuntimeProxy {
+        RuntimeProxy {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Wait until at least one command is pending.
+    pub fn wait(&self) -> impl Future<Output = ()> + Send + '_ {
+        Wait {
+            shared: &self.shared,
+        }
+    }
+
+    /// Run every pending command against the runtime, returning the
+    /// number of commands that were run.
+    pub async fn run_pending(&self, runtime: &mut dyn Runtime) -> usize {
+        let mut count = 0;
+
+        loop {
+            // don't hold the lock while the command runs
+            let command = self.shared.commands.lock().pop_front();
+            let command = match command {
+                Some(command) => command,
+                None => return count,
+            };
+
+            if let Err(err) = command(&mut *runtime).await {
+                tracing::error!("Command sent through proxy failed: {}", &err);
+            }
+            count += 1;
+        }
+    }
+}
+
+impl Drop for ProxyQueue {
+    fn drop(&mut self) {
+        let pending = {
+            let mut commands = self.shared.commands.lock();
+            self.shared.closed.store(true, Ordering::Release);
+            core::mem::take(&mut *commands)
+        };
+
+        // dropped outside of the lock, since they may send commands of
+        // their own
+        drop(pending);
+    }
+}
+
+impl RuntimeProxy {
+    /// Send a command to be run on the runtime's thread.
+    ///
+    /// If the `ProxyQueue` has been dropped, e.g. because the runtime
+    /// shut down, the command is discarded.
+    pub fn send<F>(&self, command: F)
+    where
+        F: for<'a> FnOnce(&'a mut dyn Runtime) -> RuntimeFuture<'a> + Send + 'static,
+    {
+        let queued = {
+            let mut commands = self.shared.commands.lock();
+            let closed = self.shared.closed.load(Ordering::Acquire);
+            if !closed {
+                commands.push_back(Box::new(command));
+            }
+            !closed
+        };
+
+        if !queued {
+            tracing::warn!("Discarding command sent to a runtime that has shut down");
+            return;
+        }
+
+        if let Some(waker) = self.shared.waker.lock().take() {
+            waker.wake();
+        }
+    }
+
+    /// Run a closure on the runtime's thread, returning a handle that
+    /// resolves to the result of the future it returns.
+    ///
+    /// This is for work that has to happen on the GUI thread, like
+    /// creating native resources, but whose result is needed elsewhere.
+    /// Unlike `send`, errors are returned to the caller rather than
+    /// logged. If the `ProxyQueue` is dropped before the closure runs,
+    /// the handle resolves to an error.
+    pub fn run_on_gui_thread<F, R>(&self, f: F) -> JoinHandle<Result<R>>
+    where
+        F: for<'a> FnOnce(&'a mut dyn Runtime) -> RuntimeFuture<'a, R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (completion, handle) = completion_or(|| {
+            Err(Error::static_msg(
+                "The runtime shut down before the command could run",
+            ))
+        });
+        self.send(move |rt| {
+            let result = f(rt);
+            Box::pin(async move {
+                completion.complete(result.await);
+                Ok(())
+            })
+        });
+        handle
+    }
+
+    cfg_std! {
+        /// Run a closure on the runtime's thread, and block the current
+        /// thread until its result is ready, much like `SendMessage` on
+        /// Windows.
+        ///
+        /// This deadlocks if called from the runtime's own thread.
+        pub fn run_on_gui_thread_blocking<F, R>(&self, f: F) -> Result<R>
+        where
+            F: for<'a> FnOnce(&'a mut dyn Runtime) -> RuntimeFuture<'a, R> + Send + 'static,
+            R: Send + 'static,
+        {
+            block_on(self.run_on_gui_thread(f))
+        }
+    }
+
+    /// Set the title of a window.
+    pub fn window_set_title(&self, window: Window, title: String) {
+        self.send(move |rt| rt.window_set_title(window, &title));
+    }
+
+    /// Set the position and size of a window.
+    pub fn window_set_geometry(&self, window: Window, geometry: Rectangle) {
+        self.send(move |rt| rt.window_set_geometry(window, geometry));
+    }
+
+    /// Show, hide or iconify a window.
+    pub fn window_set_visibility(&self, window: Window, visibility: Visibility) {
+        self.send(move |rt| rt.window_set_visibility(window, visibility));
+    }
+
+    /// Raise a window above its siblings.
+    pub fn window_raise(&self, window: Window) {
+        self.send(move |rt| rt.window_raise(window));
+    }
+}
+
+struct Wait<'a> {
+    shared: &'a Shared,
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // register the waker first, so that a command sent in between
+        // the check and the registration isn't missed
+        *self.shared.waker.lock() = Some(cx.waker().clone());
+
+        if self.shared.commands.lock().is_empty() {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+cfg_std! {
+    /// Wakes a thread parked in `block_on`.
+    struct ThreadWaker(std::thread::Thread);
+
+    impl alloc::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Block the current thread until a future resolves.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+}
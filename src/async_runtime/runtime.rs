@@ -15,13 +15,909 @@
 // Public License along with gui-tools. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use alloc::boxed::Box;
+#[cfg(feature = "trace-spans")]
+use super::TracedRuntime;
+use super::{join_handle, GeneralRuntime, JoinHandle, ShutdownReport, Task};
+use crate::testing::{CallArg, MockRuntime};
+use crate::{
+    accessibility::{AccessNode, Priority},
+    AttentionType, Capabilities, ChildLayout, ClientMessage, ClipboardContent, ClipboardFormat,
+    ControlKind, Cursor, Error, Event, EventMask, HitTestHandler, HoverConfig, IdleCallback,
+    IdleId, KeyInfo, LogicalSize, Menu, Metrics, NativeEventFilter, NativeEventFilterId,
+    NativeHandle, PacingPolicy, PaintHandler, PaintStats, PhysicalSize, QueuePolicy,
+    ReconnectPolicy, Rectangle, ResizeEdge, Result, ScreenInfo, Selection, ShortcutId,
+    ShortcutScope, Theme, UserData, VideoFrame, Visibility, Window, WindowConfig, WindowInfo,
+    WindowProps, WmInfo,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{any::Any, future::Future, pin::Pin, time::Duration};
 
-/// The runtime that has all of the functionality for the windowing
-/// system.
-pub trait Runtime {}
+/// The future type returned by the methods of a `Runtime`.
+pub type RuntimeFuture<'a, T = ()> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
 
-// Trait sub-implementations
-impl<R: Runtime + ?Sized> Runtime for &mut R {}
+/// Create a `RuntimeFuture` that resolves immediately to the given result.
+pub fn ready<'a, T: Send + 'a>(result: Result<T>) -> RuntimeFuture<'a, T> {
+    Box::pin(core::future::ready(result))
+}
 
-impl<R: Runtime + ?Sized> Runtime for Box<R> {}
+/// Extension methods for `Runtime`s.
+pub trait RuntimeExt: Runtime {
+    /// Box this runtime into a `GeneralRuntime`.
+    ///
+    /// This is how runtimes provided outside of `gui-tools` are
+    /// returned from a backend's producer.
+    fn boxed(self) -> GeneralRuntime
+    where
+        Self: Sized + Send + 'static,
+    {
+        GeneralRuntime::Dynamic(Box::new(self))
+    }
+
+    /// Get the user data associated with a window, if it is of type `T`.
+    fn window_user_data_as<T: Any + Send + Sync>(
+        &mut self,
+        window: Window,
+    ) -> RuntimeFuture<'_, Option<Arc<T>>> {
+        let data = self.window_user_data(window);
+        Box::pin(async move { Ok(data.await?.and_then(|data| data.downcast().ok())) })
+    }
+
+    /// Get the size of a window in logical pixels.
+    fn window_logical_size(&mut self, window: Window) -> RuntimeFuture<'_, LogicalSize>
+    where
+        Self: Send,
+    {
+        Box::pin(async move {
+            let scale_factor = self.window_scale_factor(window).await?;
+            let geometry = self.query_window(window).await?.geometry;
+            Ok(PhysicalSize::new(geometry.width, geometry.height).to_logical(scale_factor))
+        })
+    }
+
+    /// Resize a window to the given size in logical pixels, keeping its
+    /// position.
+    fn window_set_logical_size(&mut self, window: Window, size: LogicalSize) -> RuntimeFuture<'_>
+    where
+        Self: Send,
+    {
+        Box::pin(async move {
+            let scale_factor = self.window_scale_factor(window).await?;
+            let mut geometry = self.query_window(window).await?.geometry;
+            let size = size.to_physical(scale_factor);
+            geometry.width = size.width.max(1);
+            geometry.height = size.height.max(1);
+            self.window_set_geometry(window, geometry).await
+        })
+    }
+
+    /// Spawn a future onto the runtime, returning a handle that resolves
+    /// to its output.
+    ///
+    /// See `Runtime::spawn_task`.
+    fn spawn<F>(&mut self, future: F) -> RuntimeFuture<'_, JoinHandle<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (task, handle) = join_handle(future);
+        let spawned = self.spawn_task(task);
+        Box::pin(async move {
+            spawned.await?;
+            Ok(handle)
+        })
+    }
+}
+
+impl<R: Runtime + ?Sized> RuntimeExt for R {}
+
+macro_rules! decl_runtime {
+    (
+        $(
+            $(#[$attr: meta])*
+            fn $fname: ident (&mut $this: ident $(, $pname: ident : $pty: ty)* $(,)?)
+                -> RuntimeFuture<'_ $(, $rty: ty)?> $default: block
+        )*
+    ) => {
+        /// The runtime that has all of the functionality for the windowing
+        /// system.
+        ///
+        /// Every method has a default implementation that reports the
+        /// operation as unsupported, so backends only need to implement
+        /// what their platform provides.
+        pub trait Runtime {
+            $(
+                $(#[$attr])*
+                #[allow(unused_variables)]
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> $default
+            )*
+        }
+
+        // Trait sub-implementations
+        impl<R: Runtime + ?Sized> Runtime for &mut R {
+            $(
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> {
+                    (**$this).$fname($($pname),*)
+                }
+            )*
+        }
+
+        impl<R: Runtime + ?Sized> Runtime for Box<R> {
+            $(
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> {
+                    (**$this).$fname($($pname),*)
+                }
+            )*
+        }
+
+        impl Runtime for GeneralRuntime {
+            $(
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> {
+                    match $this {
+                        GeneralRuntime::Dynamic(rt) => rt.$fname($($pname),*),
+                    }
+                }
+            )*
+        }
+
+        #[cfg(feature = "trace-spans")]
+        impl<R: Runtime> Runtime for TracedRuntime<R> {
+            $(
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> {
+                    let span = tracing::trace_span!(
+                        parent: $this.span(),
+                        "runtime_call",
+                        method = stringify!($fname),
+                        args = tracing::field::Empty,
+                    );
+                    if !span.is_disabled() {
+                        let args: Vec<alloc::string::String> = alloc::vec![$(CallArg::describe(&$pname)),*];
+                        span.record("args", &tracing::field::debug(&args));
+                    }
+
+                    let inner = $this.get_mut();
+                    let future = span.in_scope(move || inner.$fname($($pname),*));
+                    Box::pin(tracing::Instrument::instrument(future, span))
+                }
+            )*
+        }
+
+        impl Runtime for MockRuntime {
+            $(
+                fn $fname(&mut $this $(, $pname: $pty)*) -> RuntimeFuture<'_ $(, $rty)?> {
+                    let args = alloc::vec![$(CallArg::describe(&$pname)),*];
+                    ready($this.intercept::<output_type!($($rty)?)>(stringify!($fname), args))
+                }
+            )*
+        }
+    }
+}
+
+macro_rules! output_type {
+    () => {
+        ()
+    };
+    ($ty: ty) => {
+        $ty
+    };
+}
+
+decl_runtime! {
+    /// Get the optional features supported by this runtime.
+    ///
+    /// Callers should check these before using features like
+    /// transparency, which may render incorrectly when unsupported.
+    fn capabilities(&mut self) -> RuntimeFuture<'_, Capabilities> {
+        ready(Err(Error::unsupported("capabilities")))
+    }
+
+    /// Get information about the window manager currently managing
+    /// the display.
+    fn window_manager_info(&mut self) -> RuntimeFuture<'_, WmInfo> {
+        ready(Err(Error::unsupported("window_manager_info")))
+    }
+
+    /// Get the screens currently attached to the display.
+    ///
+    /// This is kept up to date as screens change, which is reported as
+    /// `EventType::ScreenConfigurationChanged`.
+    fn screens(&mut self) -> RuntimeFuture<'_, Vec<ScreenInfo>> {
+        ready(Err(Error::unsupported("screens")))
+    }
+
+    /// Get the color scheme currently preferred by the user.
+    ///
+    /// Changes are reported as `EventType::SystemThemeChanged`.
+    fn system_theme(&mut self) -> RuntimeFuture<'_, Theme> {
+        ready(Err(Error::unsupported("system_theme")))
+    }
+
+    /// Shut down the runtime, destroying every window, freeing every
+    /// cache and disconnecting from the windowing system.
+    ///
+    /// This should be called before the runtime is dropped, as resources
+    /// are not guaranteed to be released otherwise. Once the runtime has
+    /// been shut down, it should not be used again.
+    fn shutdown(&mut self) -> RuntimeFuture<'_, ShutdownReport> {
+        ready(Err(Error::unsupported("shutdown")))
+    }
+
+    /// Set the ID that desktop environments use to group this
+    /// application's windows and find its icon, e.g.
+    /// `org.example.Editor`.
+    ///
+    /// This applies to windows created afterwards without their own
+    /// `WindowProps::app_id`, so it should be called before creating
+    /// any windows. On Windows, this also sets the process's explicit
+    /// `AppUserModelID`.
+    fn set_app_id(&mut self, app_id: &str) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_app_id")))
+    }
+
+    /// Register this application with the session manager under the
+    /// given client ID, so that it can be restarted in the next session.
+    ///
+    /// On X11, this sets `SM_CLIENT_ID` on the `WM_CLIENT_LEADER` window
+    /// and connects to the session manager over XSMP.
+    fn set_session_id(&mut self, client_id: &str) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_session_id")))
+    }
+
+    /// Respond to an `EventType::SessionEnding` event.
+    ///
+    /// If `proceed` is false and the event allowed it, the session
+    /// manager is asked not to end the session. This answers
+    /// `SaveYourself` on X11 and `WM_QUERYENDSESSION` on Windows.
+    fn session_end_response(&mut self, proceed: bool) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("session_end_response")))
+    }
+
+    /// Wait for the next event from the windowing system.
+    fn next_event(&mut self) -> RuntimeFuture<'_, Event> {
+        ready(Err(Error::unsupported("next_event")))
+    }
+
+    /// Insert an event into the runtime's event stream, as though it
+    /// had been received from the windowing system.
+    ///
+    /// This is primarily useful for testing.
+    fn inject_event(&mut self, event: Event) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("inject_event")))
+    }
+
+    /// Set the policy describing how the event queue handles floods of
+    /// events.
+    fn set_queue_policy(&mut self, policy: QueuePolicy) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_queue_policy")))
+    }
+
+    /// Ask to receive X11 `ClientMessage`s of the type with the given
+    /// atom name, e.g. `_NET_WM_PING`, and return the atom.
+    ///
+    /// Messages of registered types are delivered as
+    /// `EventType::Native`, instead of being handled by the runtime or
+    /// discarded, so that applications can implement protocols the
+    /// runtime does not. This fails with an unsupported error on
+    /// platforms other than X11.
+    fn register_client_message(&mut self, name: &str) -> RuntimeFuture<'_, u32> {
+        ready(Err(Error::unsupported("register_client_message")))
+    }
+
+    /// Send an X11 `ClientMessage` to a window, e.g. to answer a
+    /// protocol registered with `register_client_message`.
+    ///
+    /// The window may belong to another client, such as the root window
+    /// or the source of a drag.
+    fn send_client_message(
+        &mut self,
+        window: Window,
+        message: ClientMessage,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("send_client_message")))
+    }
+
+    /// Add a filter that sees every raw event from the windowing
+    /// system before the runtime translates it.
+    ///
+    /// This is an escape hatch for handling messages that this crate
+    /// does not model, without forking it. Filters can drop events, or
+    /// have them delivered as `EventType::PlatformSpecific`.
+    fn add_native_event_filter(
+        &mut self,
+        filter: NativeEventFilter,
+    ) -> RuntimeFuture<'_, NativeEventFilterId> {
+        ready(Err(Error::unsupported("add_native_event_filter")))
+    }
+
+    /// Remove a filter added with `add_native_event_filter`.
+    fn remove_native_event_filter(&mut self, id: NativeEventFilterId) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("remove_native_event_filter")))
+    }
+
+    /// Tell whether the runtime is still connected to the display
+    /// server.
+    ///
+    /// Once the connection is lost, `next_event` delivers an
+    /// `EventType::DisplayDisconnected` event rather than failing
+    /// silently.
+    fn is_alive(&mut self) -> RuntimeFuture<'_, bool> {
+        ready(Err(Error::unsupported("is_alive")))
+    }
+
+    /// Set what the runtime does when its connection to the display
+    /// server is lost.
+    ///
+    /// By default, the runtime does not try to reconnect.
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_reconnect_policy")))
+    }
+
+    /// Run a future on the GUI thread, alongside the event loop.
+    ///
+    /// Whenever the task is woken, e.g. because the I/O it waits on
+    /// completed, the event loop wakes up to poll it, so the task can
+    /// safely update the UI through a `RuntimeProxy`. Backends that do
+    /// not run on top of another async runtime use an `Executor`.
+    ///
+    /// `RuntimeExt::spawn` also returns a handle to the task's output.
+    fn spawn_task(&mut self, task: Task) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("spawn_task")))
+    }
+
+    /// Run a callback whenever the event queue is empty, before the
+    /// runtime blocks waiting for more events.
+    ///
+    /// This lets applications do incremental work, like layout or cache
+    /// trimming, on the GUI thread without starving input. The runtime
+    /// runs at most `DEFAULT_IDLE_BUDGET` callbacks each time it becomes
+    /// idle, taking turns between them. A callback is removed once it
+    /// returns `IdleStatus::Done`.
+    fn on_idle(&mut self, callback: IdleCallback) -> RuntimeFuture<'_, IdleId> {
+        ready(Err(Error::unsupported("on_idle")))
+    }
+
+    /// Remove a callback registered with `on_idle`.
+    fn cancel_idle(&mut self, id: IdleId) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("cancel_idle")))
+    }
+
+    /// Set the longest time between clicks that still counts as a
+    /// double or triple click.
+    ///
+    /// Backends default to the system's setting where there is one, and
+    /// to `DEFAULT_DOUBLE_CLICK_INTERVAL` otherwise.
+    fn set_double_click_interval(&mut self, interval: Duration) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_double_click_interval")))
+    }
+
+    /// Set the policy describing how painting is paced.
+    fn set_pacing_policy(&mut self, policy: PacingPolicy) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_pacing_policy")))
+    }
+
+    /// Set how many worker threads the runtime uses to rasterize in
+    /// software, e.g. when converting images for upload or filling
+    /// gradients, or zero to do it all on the GUI thread.
+    ///
+    /// Backends that rasterize in software keep a `PaintPool` of this
+    /// size, and upload its results on the GUI thread. This keeps
+    /// repaints of large windows within a frame. By default, no workers
+    /// are used.
+    fn set_paint_workers(&mut self, count: usize) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_paint_workers")))
+    }
+
+    /// Set how many bytes of uploaded images the runtime keeps on the
+    /// server before evicting the least recently used ones.
+    ///
+    /// Backends default to `DEFAULT_IMAGE_CACHE_BUDGET`.
+    fn set_image_cache_budget(&mut self, bytes: usize) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_image_cache_budget")))
+    }
+
+    /// Free every image the runtime has uploaded to the server, e.g.
+    /// after closing a document full of images.
+    ///
+    /// Images are uploaded again the next time they are drawn.
+    fn purge_image_cache(&mut self) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("purge_image_cache")))
+    }
+
+    /// Get the counters describing the work done by the event loop, such
+    /// as the number of events processed and the time spent painting.
+    fn metrics(&mut self) -> RuntimeFuture<'_, Metrics> {
+        ready(Err(Error::unsupported("metrics")))
+    }
+
+    /// Get the counters describing how exposures have been serviced.
+    fn paint_stats(&mut self) -> RuntimeFuture<'_, PaintStats> {
+        ready(Err(Error::unsupported("paint_stats")))
+    }
+
+    /// Set or remove the paint handler for a window.
+    ///
+    /// When the pacing policy enables `direct_expose`, exposures are
+    /// passed to this handler directly by the backend rather than being
+    /// delivered as `EventType::Expose`.
+    fn window_set_paint_handler(
+        &mut self,
+        window: Window,
+        handler: Option<PaintHandler>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_paint_handler")))
+    }
+
+    /// Register a keyboard shortcut, which is delivered as
+    /// `EventType::Shortcut` instead of `EventType::KeyDown` when
+    /// pressed.
+    ///
+    /// Returns an error of kind `ErrorKind::Conflict` if the key
+    /// combination is already registered, either by this application or,
+    /// for global shortcuts, by another one.
+    fn register_shortcut(
+        &mut self,
+        key: KeyInfo,
+        scope: ShortcutScope,
+    ) -> RuntimeFuture<'_, ShortcutId> {
+        ready(Err(Error::unsupported("register_shortcut")))
+    }
+
+    /// Unregister a keyboard shortcut.
+    fn unregister_shortcut(&mut self, id: ShortcutId) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("unregister_shortcut")))
+    }
+
+    /// Create a new window.
+    fn create_window(&mut self, props: WindowProps) -> RuntimeFuture<'_, Window> {
+        ready(Err(Error::unsupported("create_window")))
+    }
+
+    /// Create a native control, such as a button, as a child window.
+    ///
+    /// `props.parent` must be set, and `props.title` is the control's
+    /// initial text. What the user does with the control is delivered to
+    /// it as `EventType::ControlCommand` events. See
+    /// `Capabilities::native_controls`.
+    fn create_control(
+        &mut self,
+        kind: ControlKind,
+        props: WindowProps,
+    ) -> RuntimeFuture<'_, Window> {
+        ready(Err(Error::unsupported("create_control")))
+    }
+
+    /// Get the current text of a native control, e.g. what the user typed
+    /// into an edit control.
+    fn control_text(&mut self, window: Window) -> RuntimeFuture<'_, String> {
+        ready(Err(Error::unsupported("control_text")))
+    }
+
+    /// Enable or disable automatic layout of child windows.
+    ///
+    /// When enabled, child windows with a layout attached through
+    /// `window_set_layout` are repositioned and resized whenever their
+    /// parent is resized, before the parent's `EventType::Resized` event
+    /// is delivered.
+    fn set_auto_layout(&mut self, enabled: bool) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("set_auto_layout")))
+    }
+
+    /// Attach a layout to a child window, or detach it if `layout` is
+    /// `None`.
+    fn window_set_layout(
+        &mut self,
+        window: Window,
+        layout: Option<ChildLayout>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_layout")))
+    }
+
+    /// Destroy a window and all of its children.
+    ///
+    /// The backend's resources for the windows are released, and an
+    /// `EventType::Destroyed` event is delivered for each of them. The
+    /// same happens automatically when a window is destroyed by
+    /// something other than this runtime.
+    fn destroy_window(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("destroy_window")))
+    }
+
+    /// Send any buffered requests to the windowing system.
+    ///
+    /// Runtimes that do not buffer requests have nothing to flush.
+    fn flush(&mut self) -> RuntimeFuture<'_> {
+        ready(Ok(()))
+    }
+
+    /// Get the formats that the data in a selection is available in.
+    ///
+    /// This is negotiated through the `TARGETS` target on X11.
+    fn clipboard_formats(&mut self, selection: Selection) -> RuntimeFuture<'_, Vec<ClipboardFormat>> {
+        ready(Err(Error::unsupported("clipboard_formats")))
+    }
+
+    /// Read the data in a selection in the given format.
+    ///
+    /// Large transfers are handled transparently, e.g. with the `INCR`
+    /// protocol on X11.
+    fn clipboard_read(
+        &mut self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> RuntimeFuture<'_, ClipboardContent> {
+        ready(Err(Error::unsupported("clipboard_read")))
+    }
+
+    /// Place data in a selection, replacing what was there.
+    ///
+    /// Each item in `contents` is the same data in a different format,
+    /// so that other applications can pick the one they understand.
+    fn clipboard_write(
+        &mut self,
+        selection: Selection,
+        contents: Vec<ClipboardContent>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("clipboard_write")))
+    }
+
+    /// Get the windows that currently exist in this runtime.
+    fn windows(&mut self) -> RuntimeFuture<'_, Vec<Window>> {
+        ready(Err(Error::unsupported("windows")))
+    }
+
+    /// Query the attributes of a window.
+    ///
+    /// Backends should issue all of the requests needed to fill in the
+    /// `WindowInfo` before waiting on any of their replies, so that this
+    /// costs a single round trip to the windowing system.
+    fn query_window(&mut self, window: Window) -> RuntimeFuture<'_, WindowInfo> {
+        ready(Err(Error::unsupported("query_window")))
+    }
+
+    /// Get the number of physical pixels per logical pixel for a
+    /// window, which is the scale factor of the screen it is on.
+    ///
+    /// This changes when the window moves to another screen, or along
+    /// with `EventType::ScreenConfigurationChanged`.
+    fn window_scale_factor(&mut self, window: Window) -> RuntimeFuture<'_, f64> {
+        ready(Err(Error::unsupported("window_scale_factor")))
+    }
+
+    /// Set the position and size of a window, relative to its parent.
+    fn window_set_geometry(&mut self, window: Window, geometry: Rectangle) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_geometry")))
+    }
+
+    /// Ask for an `EventType::RedrawRequested` event to be delivered for
+    /// a window at the next refresh of its monitor.
+    ///
+    /// This uses the Present extension on X11 and DWM's frame timing on
+    /// Windows. Several requests before the next refresh result in a
+    /// single event.
+    fn window_request_redraw(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_request_redraw")))
+    }
+
+    /// Mark part of a window as needing to be repainted.
+    ///
+    /// Invalidations are batched, and flushed as `EventType::Expose`
+    /// right before the runtime waits for the next event, so calling
+    /// this many times while handling one event is cheap.
+    fn window_invalidate(&mut self, window: Window, rect: Rectangle) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_invalidate")))
+    }
+
+    /// Present a window's back buffer, telling the compositor that only
+    /// the `damage` rectangles have changed since the last present.
+    ///
+    /// Compositors then only recomposite the changed areas, which saves
+    /// a lot of power when small regions, like a text caret, change
+    /// every frame. This uses the update region of `PresentPixmap` on
+    /// X11 and the dirty rectangles of `IDXGISwapChain1::Present1` on
+    /// Windows. An empty `damage` presents the whole window.
+    fn window_present_with_damage(
+        &mut self,
+        window: Window,
+        damage: &[Rectangle],
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_present_with_damage")))
+    }
+
+    /// Present a frame of video in a window, scaled to fill `dest`.
+    ///
+    /// Backends present YUV formats without converting them where they
+    /// can, e.g. through XVideo on X11, and otherwise fall back to
+    /// `VideoFrame::to_image`.
+    fn window_present_frame(
+        &mut self,
+        window: Window,
+        frame: &VideoFrame,
+        dest: Rectangle,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_present_frame")))
+    }
+
+    /// Set the title of a window.
+    fn window_set_title(&mut self, window: Window, title: &str) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_title")))
+    }
+
+    /// Apply several changes to a window at once.
+    ///
+    /// Rather than sending one request per change, backends coalesce the
+    /// changes and buffer them until the next call to `flush`, which
+    /// makes this suitable for high-frequency layout passes.
+    fn window_configure_batch(
+        &mut self,
+        window: Window,
+        config: WindowConfig,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_configure_batch")))
+    }
+
+    /// Move a window into a new parent, placing it at the given
+    /// coordinates relative to that parent.
+    ///
+    /// This uses `XReparentWindow` on X11 and `SetParent` on Windows.
+    fn window_reparent(
+        &mut self,
+        window: Window,
+        new_parent: Window,
+        x: i32,
+        y: i32,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_reparent")))
+    }
+
+    /// Raise a window above all of its siblings.
+    fn window_raise(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_raise")))
+    }
+
+    /// Bring a top-level window to the front and give it the keyboard
+    /// focus.
+    ///
+    /// This uses `_NET_ACTIVE_WINDOW` on X11 and `SetForegroundWindow`
+    /// on Windows. If the window manager refuses to steal the focus,
+    /// the window requests the user's attention instead, e.g. by
+    /// flashing its taskbar button with `FlashWindowEx`.
+    fn window_activate(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_activate")))
+    }
+
+    /// Let the window manager move a top-level window with the pointer,
+    /// as though its title bar were being dragged.
+    ///
+    /// This is for windows that draw their own title bar, and should be
+    /// called while handling the `EventType::MouseButtonDown` that starts
+    /// the drag. It uses `_NET_WM_MOVERESIZE` on X11, and sends
+    /// `WM_NCLBUTTONDOWN` with `HTCAPTION` on Windows.
+    fn window_begin_interactive_move(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_begin_interactive_move")))
+    }
+
+    /// Let the window manager resize a top-level window with the
+    /// pointer, as though the given edge were being dragged.
+    ///
+    /// See `window_begin_interactive_move`.
+    fn window_begin_interactive_resize(
+        &mut self,
+        window: Window,
+        edge: ResizeEdge,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_begin_interactive_resize")))
+    }
+
+    /// Set or remove the function that tells what part of a window's
+    /// chrome lies under a point.
+    ///
+    /// See `HitTestHandler`.
+    fn window_set_hit_test(
+        &mut self,
+        window: Window,
+        handler: Option<HitTestHandler>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_hit_test")))
+    }
+
+    /// Lower a window below all of its siblings.
+    fn window_lower(&mut self, window: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_lower")))
+    }
+
+    /// Place a window directly above `sibling`, which must have the same
+    /// parent.
+    fn window_restack_above(&mut self, window: Window, sibling: Window) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_restack_above")))
+    }
+
+    /// Show, hide or iconify a window.
+    ///
+    /// Iconifying is done with `XIconifyWindow` on X11 and
+    /// `ShowWindow(SW_MINIMIZE)` on Windows.
+    fn window_set_visibility(
+        &mut self,
+        window: Window,
+        visibility: Visibility,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_visibility")))
+    }
+
+    /// Place the text caret in a window, or remove it if `caret` is
+    /// `None`.
+    ///
+    /// The runtime blinks the caret at the system's blink interval,
+    /// delivering an `EventType::Expose` for only the caret's rectangle
+    /// each time it toggles. While painting, `window_caret_visible` tells
+    /// whether the caret should be drawn.
+    fn window_set_caret(&mut self, window: Window, caret: Option<Rectangle>) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_caret")))
+    }
+
+    /// Tell whether the caret in a window is currently in the visible
+    /// phase of its blink.
+    fn window_caret_visible(&mut self, window: Window) -> RuntimeFuture<'_, bool> {
+        ready(Err(Error::unsupported("window_caret_visible")))
+    }
+
+    /// Set whether a window's title bar is drawn light or dark, or `None`
+    /// to follow the system's preferred color scheme, which is the
+    /// default.
+    ///
+    /// Windows that follow the system are updated whenever
+    /// `EventType::SystemThemeChanged` is delivered. This uses
+    /// `DWMWA_USE_IMMERSIVE_DARK_MODE` on Windows and the
+    /// `_GTK_THEME_VARIANT` hint on X11.
+    fn window_set_titlebar_theme(
+        &mut self,
+        window: Window,
+        theme: Option<Theme>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_titlebar_theme")))
+    }
+
+    /// Set the opacity of a whole window, from 0.0 for invisible to 1.0
+    /// for opaque, e.g. to fade it in or out.
+    ///
+    /// Values outside of that range are clamped. This is cheaper than
+    /// per-pixel transparency and needs no special visual: it uses
+    /// `SetLayeredWindowAttributes` on Windows and
+    /// `_NET_WM_WINDOW_OPACITY` on X11, where it only has an effect
+    /// while a compositor is running.
+    fn window_set_alpha(&mut self, window: Window, alpha: f32) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_alpha")))
+    }
+
+    /// Ask for the user's attention without taking focus, e.g. when a
+    /// background window has something to report.
+    ///
+    /// This sets the urgency hint in `WM_HINTS` and, for critical
+    /// requests, `_NET_WM_STATE_DEMANDS_ATTENTION` on X11, and uses
+    /// `FlashWindowEx` on Windows. The request is cleared once the window
+    /// is focused.
+    fn window_request_attention(
+        &mut self,
+        window: Window,
+        attention: AttentionType,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_request_attention")))
+    }
+
+    /// Set whether or not a window is left out of the taskbar.
+    ///
+    /// This uses `_NET_WM_STATE_SKIP_TASKBAR` on X11 and
+    /// `WS_EX_TOOLWINDOW` on Windows.
+    fn window_set_skip_taskbar(&mut self, window: Window, skip: bool) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_skip_taskbar")))
+    }
+
+    /// Associate arbitrary data with a window, or remove it if `data` is
+    /// `None`.
+    ///
+    /// This lets toolkits keep their widget state with the window it
+    /// belongs to, rather than in a separate map. The data is dropped
+    /// when the window is destroyed.
+    fn window_set_user_data(&mut self, window: Window, data: Option<UserData>) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_user_data")))
+    }
+
+    /// Get the data associated with a window, if any.
+    ///
+    /// `RuntimeExt::window_user_data_as` also downcasts it to a concrete
+    /// type.
+    fn window_user_data(&mut self, window: Window) -> RuntimeFuture<'_, Option<UserData>> {
+        ready(Err(Error::unsupported("window_user_data")))
+    }
+
+    /// Get the native handles backing a window.
+    ///
+    /// With the `raw-window-handle` feature, these can be converted to
+    /// `raw-window-handle`'s types to render into the window with other
+    /// libraries.
+    fn window_native_handle(&mut self, window: Window) -> RuntimeFuture<'_, NativeHandle> {
+        ready(Err(Error::unsupported("window_native_handle")))
+    }
+
+    /// Embed a window belonging to another library or process inside a
+    /// window, at the given geometry relative to it.
+    ///
+    /// This is how webviews or video widgets are hosted. It uses the
+    /// XEmbed protocol on X11 and `SetParent` on Windows. The foreign
+    /// window keeps its own event handling.
+    fn window_embed_foreign(
+        &mut self,
+        window: Window,
+        foreign: NativeHandle,
+        geometry: Rectangle,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_embed_foreign")))
+    }
+
+    /// Set the categories of events a window receives.
+    ///
+    /// On X11, this changes the window's event mask on the server, so
+    /// that unwanted events, like pointer motion, are never sent.
+    fn window_set_event_mask(&mut self, window: Window, mask: EventMask) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_event_mask")))
+    }
+
+    /// Set when a window receives `EventType::MouseHover` and
+    /// `EventType::LongPress` events.
+    ///
+    /// Neither is reported until this is called. Hovers are only
+    /// detected while the window's event mask includes
+    /// `EventMask::POINTER_MOTION`.
+    fn window_set_hover_config(&mut self, window: Window, config: HoverConfig) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_hover_config")))
+    }
+
+    /// Set the cursor displayed while the pointer is over a window.
+    ///
+    /// Backends should cache the native cursors created for
+    /// `Cursor::Custom`, e.g. in a `CursorCache`, so that setting the
+    /// same cursor repeatedly is cheap.
+    fn window_set_cursor(&mut self, window: Window, cursor: Cursor) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_cursor")))
+    }
+
+    /// Set the menu bar displayed for a window, or remove it if `menu`
+    /// is `None`.
+    ///
+    /// Activated items are reported as `EventType::MenuItemActivated`.
+    fn window_set_menu_bar(&mut self, window: Window, menu: Option<Menu>) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_menu_bar")))
+    }
+
+    /// Set the tree describing a window's contents to assistive
+    /// technologies, or remove it if `tree` is `None`.
+    ///
+    /// The tree is replaced wholesale, so applications should call this
+    /// again whenever their contents change.
+    fn window_set_access_tree(
+        &mut self,
+        window: Window,
+        tree: Option<AccessNode>,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_set_access_tree")))
+    }
+
+    /// Ask screen readers to read out `text`.
+    ///
+    /// See `accessibility::announce`.
+    fn access_announce(&mut self, text: &str, priority: Priority) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("access_announce")))
+    }
+
+    /// Display a context menu at the given coordinates, relative to
+    /// the window.
+    ///
+    /// Activated items are reported as `EventType::MenuItemActivated`.
+    fn window_popup_context_menu(
+        &mut self,
+        window: Window,
+        menu: Menu,
+        x: i32,
+        y: i32,
+    ) -> RuntimeFuture<'_> {
+        ready(Err(Error::unsupported("window_popup_context_menu")))
+    }
+}
@@ -16,8 +16,8 @@
 // <https://www.gnu.org/licenses/>.
 
 use super::GeneralRuntime;
-use crate::Result;
-use alloc::{boxed::Box, vec, vec::Vec};
+use crate::{DpiAwareness, Error, EventDelivery, EventQueue, Result};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use core::{future::Future, pin::Pin};
 
 impl RuntimeBuilder {
@@ -59,6 +59,18 @@ macro_rules! decl_backend_info {
             $(#[$attr: meta])*
             $fname: ident : $fty: ty
         ),*
+        $(;
+            $(
+                $(#[$oattr: meta])*
+                $oname: ident / $ogetter: ident : $oty: ty
+            ),*
+            $(;
+                $(
+                    $(#[$pattr: meta])*
+                    $pname: ident : $pty: ty
+                ),*
+            )?
+        )?
     ) => {
         /// Information about a backend's operation.
         #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -69,7 +81,9 @@ macro_rules! decl_backend_info {
         /// A builder used to select the runtime to use.
         #[derive(Debug, Clone, Default)]
         pub struct RuntimeBuilder {
-            $($fname: Option<$fty>),*
+            $($fname: Option<$fty>,)*
+            $($($oname: $oty,)*)?
+            $($($($(#[$pattr])* $pname: $pty,)*)?)?
         }
 
         impl RuntimeBuilder {
@@ -81,6 +95,19 @@ macro_rules! decl_backend_info {
                 }
             )*
 
+            $($(
+                $(#[$oattr])*
+                pub fn $oname(&mut self, $oname: $oty) -> &mut Self {
+                    self.$oname = $oname;
+                    self
+                }
+
+                $(#[$oattr])*
+                pub fn $ogetter(&self) -> $oty {
+                    self.$oname
+                }
+            )*)?
+
             /// Tell if this builder matches the given backend info.
             fn matches(&self, backend: &BackendInfo) -> bool {
                 $(
@@ -101,10 +128,74 @@ decl_backend_info! {
     name: &'static str,
     /// Whether or not the backend supports hardware acceleration
     /// of any kind.
-    hardware_accelerated: bool
+    hardware_accelerated: bool;
+
+    /// The DPI awareness that the backend opts the process into at
+    /// startup.
+    ///
+    /// This is not used to select a backend, but is passed to the
+    /// backend that is selected.
+    dpi_awareness / get_dpi_awareness: DpiAwareness;
+
+    /// The names of the backends to try first, in order of preference.
+    preference: Vec<String>,
+    /// Creates the event delivery for the selected backend.
+    delivery: Option<fn() -> Box<dyn EventDelivery>>
+}
+
+impl RuntimeBuilder {
+    /// Set the names of the backends to try first, in order of
+    /// preference.
+    ///
+    /// Backends that are not listed are tried afterwards, in the order
+    /// they were registered. A name that matches no registered backend
+    /// is logged as a warning when the runtime is created, and otherwise
+    /// ignored. The `GUI_TOOLS_BACKEND` environment variable overrides
+    /// this: if it is set to a comma-separated list of backend names,
+    /// only those backends are tried, in that order.
+    pub fn preference<I>(&mut self, preference: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.preference = preference.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Get the names of the backends to try first, in order of
+    /// preference.
+    pub fn get_preference(&self) -> &[String] {
+        &self.preference
+    }
+
+    /// Set how the selected backend holds events between receiving them
+    /// and handing them to `next_event`.
+    ///
+    /// By default, this is an `EventQueue`.
+    pub fn delivery(&mut self, delivery: fn() -> Box<dyn EventDelivery>) -> &mut Self {
+        self.delivery = Some(delivery);
+        self
+    }
+
+    /// Create the event delivery that the selected backend should use.
+    ///
+    /// Backends call this from their `Producer`.
+    pub fn event_delivery(&self) -> Box<dyn EventDelivery> {
+        match self.delivery {
+            Some(delivery) => delivery(),
+            None => Box::new(EventQueue::new()),
+        }
+    }
 }
 
+/// The environment variable that overrides which backends are tried.
+pub const BACKEND_ENV_VAR: &str = "GUI_TOOLS_BACKEND";
+
 /// A backend to be inserted into the backend list.
+///
+/// Third-party crates can register their own backends with
+/// `add_backend`, producing them as `GeneralRuntime::Dynamic` (see
+/// `RuntimeExt::boxed`).
 #[derive(Copy, Clone)]
 pub struct Backend {
     /// Information about this backend.
@@ -113,7 +204,26 @@ pub struct Backend {
     pub producer: Producer,
 }
 
-type Producer = fn(&RuntimeBuilder) -> Pin<Box<dyn Future<Output = Result<GeneralRuntime>> + Send + 'static>>;
+impl Backend {
+    /// Create a new `Backend` from its information and producer.
+    pub fn new(info: BackendInfo, producer: Producer) -> Self {
+        Backend { info, producer }
+    }
+}
+
+/// A function that produces a backend, given the builder that selected it.
+pub type Producer = fn(&RuntimeBuilder) -> Pin<Box<dyn Future<Output = Result<GeneralRuntime>> + Send + 'static>>;
+
+/// Wrap a newly produced backend in a `TracedRuntime`.
+#[cfg(feature = "trace-spans")]
+fn instrument(runtime: GeneralRuntime, name: &'static str) -> GeneralRuntime {
+    GeneralRuntime::Dynamic(Box::new(super::TracedRuntime::new(runtime, name)))
+}
+
+#[cfg(not(feature = "trace-spans"))]
+fn instrument(runtime: GeneralRuntime, _name: &'static str) -> GeneralRuntime {
+    runtime
+}
 
 /// A list of backends to be used by the runtime.
 struct BackendList {
@@ -154,21 +264,150 @@ impl BackendList {
 
     async fn find_backend(&self, builder: &RuntimeBuilder) -> Result<GeneralRuntime> {
         let list = self.copy_out_list();
+        if list.is_empty() {
+            return Err(Error::static_msg("No backends are registered"));
+        }
+
+        let (candidates, mut errors) = match std::env::var(BACKEND_ENV_VAR) {
+            Ok(names) if !names.trim().is_empty() => {
+                tracing::debug!("Selecting backends from {}: {}", BACKEND_ENV_VAR, &names);
+                let names: Vec<&str> = names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .collect();
+                override_order(&list, &names)
+            }
+            _ => (preference_order(list, builder.get_preference()), Vec::new()),
+        };
+
+        for backend in candidates {
+            let name = backend.info.name;
 
-        let mut last_error = None;
-        for backend in list {
             // try to instantiate the backend, if it matches
-            if builder.matches(&backend.info) {
-                match (backend.producer)(builder).await {
-                    Ok(runtime) => return Ok(runtime),
-                    Err(e) => {
-                        tracing::warn!("Could not generate backend {}: {}", backend.info.name, &e);
-                        last_error = Some(e);
-                    }
+            if !builder.matches(&backend.info) {
+                errors.push(Error::backend(name, "does not match the requested properties"));
+                continue;
+            }
+
+            match (backend.producer)(builder).await {
+                Ok(runtime) => return Ok(instrument(runtime, name)),
+                Err(e) => {
+                    tracing::warn!("Could not generate backend {}: {}", name, &e);
+                    errors.push(e);
                 }
             }
         }
 
-        Err(last_error.unwrap_or_else(|| todo!()))
+        // report why each backend was passed over, in the order they were
+        // tried
+        Err(errors
+            .into_iter()
+            .fold(Error::static_msg("No backend could be started"), Error::chain))
+    }
+}
+
+/// Order backends so that those named in `preference` come first, in
+/// that order, followed by the rest in the order they were registered.
+///
+/// Names that match no registered backend are logged and skipped.
+fn preference_order(mut list: Vec<Backend>, preference: &[String]) -> Vec<Backend> {
+    let mut ordered = Vec::with_capacity(list.len());
+    for name in preference {
+        match list.iter().position(|backend| backend.info.name == *name) {
+            Some(index) => ordered.push(list.remove(index)),
+            None if ordered.iter().any(|backend| backend.info.name == *name) => {}
+            None => tracing::warn!("Preferred backend {:?} is not registered", name),
+        }
+    }
+
+    ordered.extend(list);
+    ordered
+}
+
+/// Pick only the backends named by the environment variable, in that
+/// order, along with errors for the names that are not registered.
+fn override_order(list: &[Backend], names: &[&str]) -> (Vec<Backend>, Vec<Error>) {
+    let mut ordered = Vec::with_capacity(names.len());
+    let mut errors = Vec::new();
+    for name in names {
+        match list.iter().find(|backend| backend.info.name == *name) {
+            Some(backend) => ordered.push(*backend),
+            None => errors.push(Error::invalid_argument(format!(
+                "no backend named {:?} is registered",
+                name
+            ))),
+        }
+    }
+
+    (ordered, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorKind;
+    use alloc::string::ToString;
+
+    fn unused(_: &RuntimeBuilder) -> Pin<Box<dyn Future<Output = Result<GeneralRuntime>> + Send>> {
+        Box::pin(async { Err(Error::static_msg("not produced in tests")) })
+    }
+
+    fn backends(names: &[&'static str]) -> Vec<Backend> {
+        names
+            .iter()
+            .map(|&name| {
+                let info = BackendInfo {
+                    name,
+                    hardware_accelerated: false,
+                };
+                Backend::new(info, unused)
+            })
+            .collect()
+    }
+
+    fn names(list: &[Backend]) -> Vec<&'static str> {
+        list.iter().map(|backend| backend.info.name).collect()
+    }
+
+    fn strings(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn preferred_backends_come_first() {
+        let list = backends(&["x11", "wayland", "headless"]);
+        let ordered = preference_order(list, &strings(&["headless", "wayland"]));
+        assert_eq!(names(&ordered), ["headless", "wayland", "x11"]);
+    }
+
+    #[test]
+    fn unknown_and_repeated_preferences_are_skipped() {
+        let list = backends(&["x11", "wayland"]);
+        let ordered = preference_order(list, &strings(&["wayland", "sixel", "wayland"]));
+        assert_eq!(names(&ordered), ["wayland", "x11"]);
+    }
+
+    #[test]
+    fn no_preference_keeps_registration_order() {
+        let list = backends(&["x11", "wayland"]);
+        assert_eq!(names(&preference_order(list, &[])), ["x11", "wayland"]);
+    }
+
+    #[test]
+    fn override_picks_only_named_backends() {
+        let list = backends(&["x11", "wayland", "headless"]);
+        let (ordered, errors) = override_order(&list, &["headless", "x11"]);
+        assert_eq!(names(&ordered), ["headless", "x11"]);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn override_reports_unknown_names() {
+        let list = backends(&["x11"]);
+        let (ordered, errors) = override_order(&list, &["sixel", "x11"]);
+        assert_eq!(names(&ordered), ["x11"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), ErrorKind::InvalidArgument);
     }
 }
@@ -0,0 +1,30 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+/// A report of the resources released when a runtime was shut down.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ShutdownReport {
+    /// The number of windows that were still open, and were destroyed
+    /// by the shutdown.
+    pub leaked_windows: usize,
+    /// The number of graphics contexts, or the platform's equivalent,
+    /// that were freed.
+    pub freed_gcs: usize,
+    /// The number of events that were still waiting to be received, and
+    /// were discarded.
+    pub pending_events: usize,
+}
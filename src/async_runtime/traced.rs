@@ -0,0 +1,69 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use tracing::Span;
+
+/// A `Runtime` that wraps every call to another runtime in a `tracing`
+/// span.
+///
+/// Each call gets a `runtime_call` span recording the method's name and
+/// arguments, including the windows involved, which is entered while
+/// the call's future is polled. Subscribers that time spans, like
+/// `tracing-subscriber`'s `FmtSpan::CLOSE`, can report how long each
+/// call took. The call spans are children of a single `event_loop` span
+/// for the whole runtime.
+///
+/// `select_runtime` wraps the backend it selects in a `TracedRuntime`
+/// when the `trace-spans` feature is enabled, so backends do not need to
+/// instrument themselves.
+#[derive(Debug)]
+pub struct TracedRuntime<R> {
+    inner: R,
+    span: Span,
+}
+
+impl<R> TracedRuntime<R> {
+    /// Wrap a runtime, naming it `backend` in the `event_loop` span.
+    pub fn new(inner: R, backend: &'static str) -> Self {
+        TracedRuntime {
+            inner,
+            span: tracing::info_span!("event_loop", backend),
+        }
+    }
+
+    /// Get the `event_loop` span that every call span belongs to.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Get the wrapped runtime.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get the wrapped runtime mutably.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwrap the wrapped runtime.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+// the `Runtime` implementation for `TracedRuntime` is generated
+// alongside the trait, in runtime.rs
@@ -0,0 +1,54 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+/// The optional features supported by a runtime.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// A compositor is running, so windows are drawn offscreen and then
+    /// composited onto the screen.
+    ///
+    /// This can change while the runtime is running, in which case an
+    /// `EventType::CompositorChanged` event is delivered.
+    pub compositing: bool,
+    /// Windows can be transparent, or faded in and out.
+    ///
+    /// On X11, this requires a compositor, as transparent areas are
+    /// drawn black otherwise.
+    pub transparency: bool,
+    /// The primary selection is available, separately from the
+    /// clipboard.
+    pub primary_selection: bool,
+    /// Images can be copied to and pasted from the clipboard, as
+    /// `ClipboardContent::Image`.
+    pub clipboard_images: bool,
+    /// The X Render extension is available, so drawing is antialiased
+    /// and composited with alpha on the server.
+    ///
+    /// This is always false outside of X11.
+    pub xrender: bool,
+    /// Gradients are filled by the windowing system, rather than being
+    /// rasterized in software.
+    pub gradients: bool,
+    /// Native controls can be created with `Runtime::create_control`.
+    pub native_controls: bool,
+    /// Touch input is available.
+    pub touch: bool,
+    /// Presenting can be synchronized with the monitor's refresh, so
+    /// animations do not tear.
+    pub vsync: bool,
+}
@@ -0,0 +1,118 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::Rectangle;
+use core::time::Duration;
+
+/// The caret blink interval used when the system does not provide one.
+pub const DEFAULT_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// Tracks the blinking of a text caret.
+///
+/// This is intended for use by backends, which create one per window
+/// with the system's blink interval (`GetCaretBlinkTime` on Windows, or
+/// XSETTINGS' `Net/CursorBlinkTime` on X11) and invalidate the caret's
+/// rectangle whenever it toggles.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CaretBlinker {
+    rect: Option<Rectangle>,
+    visible: bool,
+    interval: Duration,
+    elapsed: Duration,
+}
+
+impl Default for CaretBlinker {
+    fn default() -> Self {
+        Self::new(DEFAULT_BLINK_INTERVAL)
+    }
+}
+
+impl CaretBlinker {
+    /// Create a new `CaretBlinker` with no caret, which toggles the caret
+    /// after every `interval`.
+    ///
+    /// An interval of zero disables blinking.
+    pub fn new(interval: Duration) -> Self {
+        CaretBlinker {
+            rect: None,
+            visible: true,
+            interval,
+            elapsed: Duration::from_secs(0),
+        }
+    }
+
+    /// Get the rectangle the caret occupies, if there is a caret.
+    pub fn rect(&self) -> Option<Rectangle> {
+        self.rect
+    }
+
+    /// Tell whether the caret should currently be drawn.
+    pub fn is_visible(&self) -> bool {
+        self.rect.is_some() && self.visible
+    }
+
+    /// Move the caret, or remove it if `rect` is `None`, returning the
+    /// rectangle it previously occupied.
+    ///
+    /// The caret is shown immediately and the blink restarts, so that it
+    /// does not disappear while the user is typing.
+    pub fn set_rect(&mut self, rect: Option<Rectangle>) -> Option<Rectangle> {
+        self.visible = true;
+        self.elapsed = Duration::from_secs(0);
+        core::mem::replace(&mut self.rect, rect)
+    }
+
+    /// Advance the blink by `elapsed`, returning the caret's rectangle if
+    /// it needs to be repainted.
+    pub fn advance(&mut self, elapsed: Duration) -> Option<Rectangle> {
+        let rect = self.rect?;
+        if self.interval == Duration::from_secs(0) {
+            return None;
+        }
+
+        // count the toggles at once, rather than one interval at a time,
+        // since a tiny interval and a long stall would take forever
+        const NANOS_PER_SEC: u128 = 1_000_000_000;
+        let total = self.elapsed.as_nanos() + elapsed.as_nanos();
+        let interval = self.interval.as_nanos();
+        let remainder = total % interval;
+        self.elapsed = Duration::new(
+            (remainder / NANOS_PER_SEC) as u64,
+            (remainder % NANOS_PER_SEC) as u32,
+        );
+
+        // an even number of toggles leaves the caret as it was
+        let toggled = (total / interval) % 2 == 1;
+        self.visible ^= toggled;
+
+        if toggled {
+            Some(rect)
+        } else {
+            None
+        }
+    }
+
+    /// Get the time until the caret next toggles, or `None` if it does
+    /// not blink.
+    pub fn time_until_toggle(&self) -> Option<Duration> {
+        if self.rect.is_none() || self.interval == Duration::from_secs(0) {
+            None
+        } else {
+            Some(self.interval - self.elapsed)
+        }
+    }
+}
@@ -0,0 +1,83 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::Image;
+use alloc::{string::String, vec::Vec};
+
+/// A selection that data can be transferred through.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Selection {
+    /// The clipboard, used for explicit copying and pasting.
+    Clipboard,
+    /// The primary selection, which holds whatever text was most
+    /// recently selected and is pasted with the middle mouse button.
+    ///
+    /// This only exists on X11 (see `Capabilities::primary_selection`);
+    /// other backends report it as unsupported.
+    Primary,
+}
+
+/// A format that data on the clipboard can be requested in.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClipboardFormat {
+    /// UTF-8 text.
+    Text,
+    /// An image.
+    ///
+    /// Backends convert between `Image` and whatever image format the
+    /// windowing system uses, e.g. `image/png` on X11 and `CF_DIB` on
+    /// Windows.
+    Image,
+    /// Arbitrary data, identified by its MIME type.
+    ///
+    /// On Windows, MIME types are mapped to registered clipboard
+    /// formats of the same name.
+    Mime(String),
+}
+
+/// Data that can be placed on or read from the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClipboardContent {
+    /// UTF-8 text.
+    Text(String),
+    /// An image.
+    Image(Image),
+    /// Arbitrary data, along with its MIME type.
+    Mime(String, Vec<u8>),
+}
+
+impl ClipboardFormat {
+    /// Get the MIME type used for this format.
+    pub fn mime_type(&self) -> &str {
+        match self {
+            ClipboardFormat::Text => "text/plain;charset=utf-8",
+            ClipboardFormat::Image => "image/png",
+            ClipboardFormat::Mime(mime_type) => mime_type,
+        }
+    }
+}
+
+impl ClipboardContent {
+    /// Get the format of this content.
+    pub fn format(&self) -> ClipboardFormat {
+        match self {
+            ClipboardContent::Text(_) => ClipboardFormat::Text,
+            ClipboardContent::Image(_) => ClipboardFormat::Image,
+            ClipboardContent::Mime(mime_type, _) => ClipboardFormat::Mime(mime_type.clone()),
+        }
+    }
+}
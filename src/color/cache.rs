@@ -0,0 +1,116 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::Rgba;
+use alloc::collections::{btree_map::Entry, BTreeMap, VecDeque};
+
+/// The default number of recently used colors kept in a `ColorCache`.
+const DEFAULT_CAPACITY: usize = 32;
+
+/// A cache mapping colors to the native pixel values allocated for them.
+///
+/// Backends own one of these and share it between every path that needs
+/// pixel values (e.g. window creation and drawing), so each color only
+/// costs one allocation round trip. Colors in the standard palette are
+/// pinned once allocated, while other colors are kept in a small
+/// most-recently-used list.
+#[derive(Debug, Clone)]
+pub struct ColorCache {
+    pinned: BTreeMap<Rgba, u32>,
+    recent: VecDeque<(Rgba, u32)>,
+    capacity: usize,
+}
+
+impl Default for ColorCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl ColorCache {
+    /// Create a new, empty `ColorCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty `ColorCache` that keeps up to `capacity`
+    /// recently used colors.
+    pub fn with_capacity(capacity: usize) -> Self {
+        ColorCache {
+            pinned: BTreeMap::new(),
+            recent: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Allocate and pin every color in `palette`, typically
+    /// `colors::STANDARD_PALETTE`.
+    pub fn prefill<E>(
+        &mut self,
+        palette: &[Rgba],
+        mut alloc: impl FnMut(Rgba) -> Result<u32, E>,
+    ) -> Result<(), E> {
+        for &color in palette {
+            if let Entry::Vacant(entry) = self.pinned.entry(color) {
+                entry.insert(alloc(color)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the pixel value for a color without allocating it.
+    pub fn get(&mut self, color: Rgba) -> Option<u32> {
+        if let Some(&pixel) = self.pinned.get(&color) {
+            return Some(pixel);
+        }
+
+        // move the color to the front of the recently used list
+        let index = self.recent.iter().position(|&(c, _)| c == color)?;
+        let entry = self.recent.remove(index)?;
+        self.recent.push_front(entry);
+        Some(entry.1)
+    }
+
+    /// Get the pixel value for a color, allocating it with `alloc` if it
+    /// is not already cached.
+    pub fn get_or_alloc<E>(
+        &mut self,
+        color: Rgba,
+        alloc: impl FnOnce(Rgba) -> Result<u32, E>,
+    ) -> Result<u32, E> {
+        if let Some(pixel) = self.get(color) {
+            return Ok(pixel);
+        }
+
+        let pixel = alloc(color)?;
+        if self.capacity > 0 {
+            if self.recent.len() == self.capacity {
+                self.recent.pop_back();
+            }
+            self.recent.push_front((color, pixel));
+        }
+
+        Ok(pixel)
+    }
+
+    /// Remove every color from the cache.
+    pub fn clear(&mut self) {
+        self.pinned.clear();
+        self.recent.clear();
+    }
+}
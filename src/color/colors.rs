@@ -0,0 +1,473 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Commonly used colors.
+//!
+//! This contains every color named by CSS, which are mostly the same
+//! as those named by X11. Where the two differ, CSS is followed.
+
+use super::Rgba;
+
+/// Alice blue, `#f0f8ff`.
+pub const ALICE_BLUE: Rgba = Rgba::rgb(240, 248, 255);
+/// Antique white, `#faebd7`.
+pub const ANTIQUE_WHITE: Rgba = Rgba::rgb(250, 235, 215);
+/// Aqua, `#00ffff`.
+pub const AQUA: Rgba = Rgba::rgb(0, 255, 255);
+/// Aquamarine, `#7fffd4`.
+pub const AQUAMARINE: Rgba = Rgba::rgb(127, 255, 212);
+/// Azure, `#f0ffff`.
+pub const AZURE: Rgba = Rgba::rgb(240, 255, 255);
+/// Beige, `#f5f5dc`.
+pub const BEIGE: Rgba = Rgba::rgb(245, 245, 220);
+/// Bisque, `#ffe4c4`.
+pub const BISQUE: Rgba = Rgba::rgb(255, 228, 196);
+/// Black, `#000000`.
+pub const BLACK: Rgba = Rgba::rgb(0, 0, 0);
+/// Blanched almond, `#ffebcd`.
+pub const BLANCHED_ALMOND: Rgba = Rgba::rgb(255, 235, 205);
+/// Blue, `#0000ff`.
+pub const BLUE: Rgba = Rgba::rgb(0, 0, 255);
+/// Blue violet, `#8a2be2`.
+pub const BLUE_VIOLET: Rgba = Rgba::rgb(138, 43, 226);
+/// Brown, `#a52a2a`.
+pub const BROWN: Rgba = Rgba::rgb(165, 42, 42);
+/// Burlywood, `#deb887`.
+pub const BURLYWOOD: Rgba = Rgba::rgb(222, 184, 135);
+/// Cadet blue, `#5f9ea0`.
+pub const CADET_BLUE: Rgba = Rgba::rgb(95, 158, 160);
+/// Chartreuse, `#7fff00`.
+pub const CHARTREUSE: Rgba = Rgba::rgb(127, 255, 0);
+/// Chocolate, `#d2691e`.
+pub const CHOCOLATE: Rgba = Rgba::rgb(210, 105, 30);
+/// Coral, `#ff7f50`.
+pub const CORAL: Rgba = Rgba::rgb(255, 127, 80);
+/// Cornflower blue, `#6495ed`.
+pub const CORNFLOWER_BLUE: Rgba = Rgba::rgb(100, 149, 237);
+/// Cornsilk, `#fff8dc`.
+pub const CORNSILK: Rgba = Rgba::rgb(255, 248, 220);
+/// Crimson, `#dc143c`.
+pub const CRIMSON: Rgba = Rgba::rgb(220, 20, 60);
+/// Cyan, `#00ffff`.
+pub const CYAN: Rgba = Rgba::rgb(0, 255, 255);
+/// Dark blue, `#00008b`.
+pub const DARK_BLUE: Rgba = Rgba::rgb(0, 0, 139);
+/// Dark cyan, `#008b8b`.
+pub const DARK_CYAN: Rgba = Rgba::rgb(0, 139, 139);
+/// Dark goldenrod, `#b8860b`.
+pub const DARK_GOLDENROD: Rgba = Rgba::rgb(184, 134, 11);
+/// Dark gray, `#a9a9a9`.
+pub const DARK_GRAY: Rgba = Rgba::rgb(169, 169, 169);
+/// Dark green, `#006400`.
+pub const DARK_GREEN: Rgba = Rgba::rgb(0, 100, 0);
+/// Dark khaki, `#bdb76b`.
+pub const DARK_KHAKI: Rgba = Rgba::rgb(189, 183, 107);
+/// Dark magenta, `#8b008b`.
+pub const DARK_MAGENTA: Rgba = Rgba::rgb(139, 0, 139);
+/// Dark olive green, `#556b2f`.
+pub const DARK_OLIVE_GREEN: Rgba = Rgba::rgb(85, 107, 47);
+/// Dark orange, `#ff8c00`.
+pub const DARK_ORANGE: Rgba = Rgba::rgb(255, 140, 0);
+/// Dark orchid, `#9932cc`.
+pub const DARK_ORCHID: Rgba = Rgba::rgb(153, 50, 204);
+/// Dark red, `#8b0000`.
+pub const DARK_RED: Rgba = Rgba::rgb(139, 0, 0);
+/// Dark salmon, `#e9967a`.
+pub const DARK_SALMON: Rgba = Rgba::rgb(233, 150, 122);
+/// Dark sea green, `#8fbc8f`.
+pub const DARK_SEA_GREEN: Rgba = Rgba::rgb(143, 188, 143);
+/// Dark slate blue, `#483d8b`.
+pub const DARK_SLATE_BLUE: Rgba = Rgba::rgb(72, 61, 139);
+/// Dark slate gray, `#2f4f4f`.
+pub const DARK_SLATE_GRAY: Rgba = Rgba::rgb(47, 79, 79);
+/// Dark turquoise, `#00ced1`.
+pub const DARK_TURQUOISE: Rgba = Rgba::rgb(0, 206, 209);
+/// Dark violet, `#9400d3`.
+pub const DARK_VIOLET: Rgba = Rgba::rgb(148, 0, 211);
+/// Deep pink, `#ff1493`.
+pub const DEEP_PINK: Rgba = Rgba::rgb(255, 20, 147);
+/// Deep sky blue, `#00bfff`.
+pub const DEEP_SKY_BLUE: Rgba = Rgba::rgb(0, 191, 255);
+/// Dim gray, `#696969`.
+pub const DIM_GRAY: Rgba = Rgba::rgb(105, 105, 105);
+/// Dodger blue, `#1e90ff`.
+pub const DODGER_BLUE: Rgba = Rgba::rgb(30, 144, 255);
+/// Firebrick, `#b22222`.
+pub const FIREBRICK: Rgba = Rgba::rgb(178, 34, 34);
+/// Floral white, `#fffaf0`.
+pub const FLORAL_WHITE: Rgba = Rgba::rgb(255, 250, 240);
+/// Forest green, `#228b22`.
+pub const FOREST_GREEN: Rgba = Rgba::rgb(34, 139, 34);
+/// Fuchsia, `#ff00ff`.
+pub const FUCHSIA: Rgba = Rgba::rgb(255, 0, 255);
+/// Gainsboro, `#dcdcdc`.
+pub const GAINSBORO: Rgba = Rgba::rgb(220, 220, 220);
+/// Ghost white, `#f8f8ff`.
+pub const GHOST_WHITE: Rgba = Rgba::rgb(248, 248, 255);
+/// Gold, `#ffd700`.
+pub const GOLD: Rgba = Rgba::rgb(255, 215, 0);
+/// Goldenrod, `#daa520`.
+pub const GOLDENROD: Rgba = Rgba::rgb(218, 165, 32);
+/// Gray, `#808080`.
+pub const GRAY: Rgba = Rgba::rgb(128, 128, 128);
+/// Green, `#008000`.
+///
+/// This follows CSS; X11's brighter `green` is available as `LIME`.
+pub const GREEN: Rgba = Rgba::rgb(0, 128, 0);
+/// Green yellow, `#adff2f`.
+pub const GREEN_YELLOW: Rgba = Rgba::rgb(173, 255, 47);
+/// Honeydew, `#f0fff0`.
+pub const HONEYDEW: Rgba = Rgba::rgb(240, 255, 240);
+/// Hot pink, `#ff69b4`.
+pub const HOT_PINK: Rgba = Rgba::rgb(255, 105, 180);
+/// Indian red, `#cd5c5c`.
+pub const INDIAN_RED: Rgba = Rgba::rgb(205, 92, 92);
+/// Indigo, `#4b0082`.
+pub const INDIGO: Rgba = Rgba::rgb(75, 0, 130);
+/// Ivory, `#fffff0`.
+pub const IVORY: Rgba = Rgba::rgb(255, 255, 240);
+/// Khaki, `#f0e68c`.
+pub const KHAKI: Rgba = Rgba::rgb(240, 230, 140);
+/// Lavender, `#e6e6fa`.
+pub const LAVENDER: Rgba = Rgba::rgb(230, 230, 250);
+/// Lavender blush, `#fff0f5`.
+pub const LAVENDER_BLUSH: Rgba = Rgba::rgb(255, 240, 245);
+/// Lawn green, `#7cfc00`.
+pub const LAWN_GREEN: Rgba = Rgba::rgb(124, 252, 0);
+/// Lemon chiffon, `#fffacd`.
+pub const LEMON_CHIFFON: Rgba = Rgba::rgb(255, 250, 205);
+/// Light blue, `#add8e6`.
+pub const LIGHT_BLUE: Rgba = Rgba::rgb(173, 216, 230);
+/// Light coral, `#f08080`.
+pub const LIGHT_CORAL: Rgba = Rgba::rgb(240, 128, 128);
+/// Light cyan, `#e0ffff`.
+pub const LIGHT_CYAN: Rgba = Rgba::rgb(224, 255, 255);
+/// Light goldenrod yellow, `#fafad2`.
+pub const LIGHT_GOLDENROD_YELLOW: Rgba = Rgba::rgb(250, 250, 210);
+/// Light gray, `#d3d3d3`.
+pub const LIGHT_GRAY: Rgba = Rgba::rgb(211, 211, 211);
+/// Light green, `#90ee90`.
+pub const LIGHT_GREEN: Rgba = Rgba::rgb(144, 238, 144);
+/// Light pink, `#ffb6c1`.
+pub const LIGHT_PINK: Rgba = Rgba::rgb(255, 182, 193);
+/// Light salmon, `#ffa07a`.
+pub const LIGHT_SALMON: Rgba = Rgba::rgb(255, 160, 122);
+/// Light sea green, `#20b2aa`.
+pub const LIGHT_SEA_GREEN: Rgba = Rgba::rgb(32, 178, 170);
+/// Light sky blue, `#87cefa`.
+pub const LIGHT_SKY_BLUE: Rgba = Rgba::rgb(135, 206, 250);
+/// Light slate gray, `#778899`.
+pub const LIGHT_SLATE_GRAY: Rgba = Rgba::rgb(119, 136, 153);
+/// Light steel blue, `#b0c4de`.
+pub const LIGHT_STEEL_BLUE: Rgba = Rgba::rgb(176, 196, 222);
+/// Light yellow, `#ffffe0`.
+pub const LIGHT_YELLOW: Rgba = Rgba::rgb(255, 255, 224);
+/// Lime, `#00ff00`.
+pub const LIME: Rgba = Rgba::rgb(0, 255, 0);
+/// Lime green, `#32cd32`.
+pub const LIME_GREEN: Rgba = Rgba::rgb(50, 205, 50);
+/// Linen, `#faf0e6`.
+pub const LINEN: Rgba = Rgba::rgb(250, 240, 230);
+/// Magenta, `#ff00ff`.
+pub const MAGENTA: Rgba = Rgba::rgb(255, 0, 255);
+/// Maroon, `#800000`.
+pub const MAROON: Rgba = Rgba::rgb(128, 0, 0);
+/// Medium aquamarine, `#66cdaa`.
+pub const MEDIUM_AQUAMARINE: Rgba = Rgba::rgb(102, 205, 170);
+/// Medium blue, `#0000cd`.
+pub const MEDIUM_BLUE: Rgba = Rgba::rgb(0, 0, 205);
+/// Medium orchid, `#ba55d3`.
+pub const MEDIUM_ORCHID: Rgba = Rgba::rgb(186, 85, 211);
+/// Medium purple, `#9370db`.
+pub const MEDIUM_PURPLE: Rgba = Rgba::rgb(147, 112, 219);
+/// Medium sea green, `#3cb371`.
+pub const MEDIUM_SEA_GREEN: Rgba = Rgba::rgb(60, 179, 113);
+/// Medium slate blue, `#7b68ee`.
+pub const MEDIUM_SLATE_BLUE: Rgba = Rgba::rgb(123, 104, 238);
+/// Medium spring green, `#00fa9a`.
+pub const MEDIUM_SPRING_GREEN: Rgba = Rgba::rgb(0, 250, 154);
+/// Medium turquoise, `#48d1cc`.
+pub const MEDIUM_TURQUOISE: Rgba = Rgba::rgb(72, 209, 204);
+/// Medium violet red, `#c71585`.
+pub const MEDIUM_VIOLET_RED: Rgba = Rgba::rgb(199, 21, 133);
+/// Midnight blue, `#191970`.
+pub const MIDNIGHT_BLUE: Rgba = Rgba::rgb(25, 25, 112);
+/// Mint cream, `#f5fffa`.
+pub const MINT_CREAM: Rgba = Rgba::rgb(245, 255, 250);
+/// Misty rose, `#ffe4e1`.
+pub const MISTY_ROSE: Rgba = Rgba::rgb(255, 228, 225);
+/// Moccasin, `#ffe4b5`.
+pub const MOCCASIN: Rgba = Rgba::rgb(255, 228, 181);
+/// Navajo white, `#ffdead`.
+pub const NAVAJO_WHITE: Rgba = Rgba::rgb(255, 222, 173);
+/// Navy, `#000080`.
+pub const NAVY: Rgba = Rgba::rgb(0, 0, 128);
+/// Old lace, `#fdf5e6`.
+pub const OLD_LACE: Rgba = Rgba::rgb(253, 245, 230);
+/// Olive, `#808000`.
+pub const OLIVE: Rgba = Rgba::rgb(128, 128, 0);
+/// Olive drab, `#6b8e23`.
+pub const OLIVE_DRAB: Rgba = Rgba::rgb(107, 142, 35);
+/// Orange, `#ffa500`.
+pub const ORANGE: Rgba = Rgba::rgb(255, 165, 0);
+/// Orange red, `#ff4500`.
+pub const ORANGE_RED: Rgba = Rgba::rgb(255, 69, 0);
+/// Orchid, `#da70d6`.
+pub const ORCHID: Rgba = Rgba::rgb(218, 112, 214);
+/// Pale goldenrod, `#eee8aa`.
+pub const PALE_GOLDENROD: Rgba = Rgba::rgb(238, 232, 170);
+/// Pale green, `#98fb98`.
+pub const PALE_GREEN: Rgba = Rgba::rgb(152, 251, 152);
+/// Pale turquoise, `#afeeee`.
+pub const PALE_TURQUOISE: Rgba = Rgba::rgb(175, 238, 238);
+/// Pale violet red, `#db7093`.
+pub const PALE_VIOLET_RED: Rgba = Rgba::rgb(219, 112, 147);
+/// Papaya whip, `#ffefd5`.
+pub const PAPAYA_WHIP: Rgba = Rgba::rgb(255, 239, 213);
+/// Peach puff, `#ffdab9`.
+pub const PEACH_PUFF: Rgba = Rgba::rgb(255, 218, 185);
+/// Peru, `#cd853f`.
+pub const PERU: Rgba = Rgba::rgb(205, 133, 63);
+/// Pink, `#ffc0cb`.
+pub const PINK: Rgba = Rgba::rgb(255, 192, 203);
+/// Plum, `#dda0dd`.
+pub const PLUM: Rgba = Rgba::rgb(221, 160, 221);
+/// Powder blue, `#b0e0e6`.
+pub const POWDER_BLUE: Rgba = Rgba::rgb(176, 224, 230);
+/// Purple, `#800080`.
+pub const PURPLE: Rgba = Rgba::rgb(128, 0, 128);
+/// Rebecca purple, `#663399`.
+pub const REBECCA_PURPLE: Rgba = Rgba::rgb(102, 51, 153);
+/// Red, `#ff0000`.
+pub const RED: Rgba = Rgba::rgb(255, 0, 0);
+/// Rosy brown, `#bc8f8f`.
+pub const ROSY_BROWN: Rgba = Rgba::rgb(188, 143, 143);
+/// Royal blue, `#4169e1`.
+pub const ROYAL_BLUE: Rgba = Rgba::rgb(65, 105, 225);
+/// Saddle brown, `#8b4513`.
+pub const SADDLE_BROWN: Rgba = Rgba::rgb(139, 69, 19);
+/// Salmon, `#fa8072`.
+pub const SALMON: Rgba = Rgba::rgb(250, 128, 114);
+/// Sandy brown, `#f4a460`.
+pub const SANDY_BROWN: Rgba = Rgba::rgb(244, 164, 96);
+/// Seashell, `#fff5ee`.
+pub const SEASHELL: Rgba = Rgba::rgb(255, 245, 238);
+/// Sea green, `#2e8b57`.
+pub const SEA_GREEN: Rgba = Rgba::rgb(46, 139, 87);
+/// Sienna, `#a0522d`.
+pub const SIENNA: Rgba = Rgba::rgb(160, 82, 45);
+/// Silver, `#c0c0c0`.
+pub const SILVER: Rgba = Rgba::rgb(192, 192, 192);
+/// Sky blue, `#87ceeb`.
+pub const SKY_BLUE: Rgba = Rgba::rgb(135, 206, 235);
+/// Slate blue, `#6a5acd`.
+pub const SLATE_BLUE: Rgba = Rgba::rgb(106, 90, 205);
+/// Slate gray, `#708090`.
+pub const SLATE_GRAY: Rgba = Rgba::rgb(112, 128, 144);
+/// Snow, `#fffafa`.
+pub const SNOW: Rgba = Rgba::rgb(255, 250, 250);
+/// Spring green, `#00ff7f`.
+pub const SPRING_GREEN: Rgba = Rgba::rgb(0, 255, 127);
+/// Steel blue, `#4682b4`.
+pub const STEEL_BLUE: Rgba = Rgba::rgb(70, 130, 180);
+/// Tan, `#d2b48c`.
+pub const TAN: Rgba = Rgba::rgb(210, 180, 140);
+/// Teal, `#008080`.
+pub const TEAL: Rgba = Rgba::rgb(0, 128, 128);
+/// Thistle, `#d8bfd8`.
+pub const THISTLE: Rgba = Rgba::rgb(216, 191, 216);
+/// Tomato, `#ff6347`.
+pub const TOMATO: Rgba = Rgba::rgb(255, 99, 71);
+/// Turquoise, `#40e0d0`.
+pub const TURQUOISE: Rgba = Rgba::rgb(64, 224, 208);
+/// Violet, `#ee82ee`.
+pub const VIOLET: Rgba = Rgba::rgb(238, 130, 238);
+/// Web green, `#008000`, which is X11's name for `GREEN`.
+pub const WEB_GREEN: Rgba = Rgba::rgb(0, 128, 0);
+/// Wheat, `#f5deb3`.
+pub const WHEAT: Rgba = Rgba::rgb(245, 222, 179);
+/// White, `#ffffff`.
+pub const WHITE: Rgba = Rgba::rgb(255, 255, 255);
+/// White smoke, `#f5f5f5`.
+pub const WHITE_SMOKE: Rgba = Rgba::rgb(245, 245, 245);
+/// Yellow, `#ffff00`.
+pub const YELLOW: Rgba = Rgba::rgb(255, 255, 0);
+/// Yellow green, `#9acd32`.
+pub const YELLOW_GREEN: Rgba = Rgba::rgb(154, 205, 50);
+/// Fully transparent black.
+pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+
+/// Every named color, sorted by name, as used by `Rgba::from_name`.
+///
+/// Names are lowercase, without spaces, and both spellings of "gray"
+/// are included.
+pub const NAMED_COLORS: &[(&str, Rgba)] = &[
+    ("aliceblue", ALICE_BLUE),
+    ("antiquewhite", ANTIQUE_WHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUAMARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHED_ALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUE_VIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLYWOOD),
+    ("cadetblue", CADET_BLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWER_BLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARK_BLUE),
+    ("darkcyan", DARK_CYAN),
+    ("darkgoldenrod", DARK_GOLDENROD),
+    ("darkgray", DARK_GRAY),
+    ("darkgreen", DARK_GREEN),
+    ("darkgrey", DARK_GRAY),
+    ("darkkhaki", DARK_KHAKI),
+    ("darkmagenta", DARK_MAGENTA),
+    ("darkolivegreen", DARK_OLIVE_GREEN),
+    ("darkorange", DARK_ORANGE),
+    ("darkorchid", DARK_ORCHID),
+    ("darkred", DARK_RED),
+    ("darksalmon", DARK_SALMON),
+    ("darkseagreen", DARK_SEA_GREEN),
+    ("darkslateblue", DARK_SLATE_BLUE),
+    ("darkslategray", DARK_SLATE_GRAY),
+    ("darkslategrey", DARK_SLATE_GRAY),
+    ("darkturquoise", DARK_TURQUOISE),
+    ("darkviolet", DARK_VIOLET),
+    ("deeppink", DEEP_PINK),
+    ("deepskyblue", DEEP_SKY_BLUE),
+    ("dimgray", DIM_GRAY),
+    ("dimgrey", DIM_GRAY),
+    ("dodgerblue", DODGER_BLUE),
+    ("firebrick", FIREBRICK),
+    ("floralwhite", FLORAL_WHITE),
+    ("forestgreen", FOREST_GREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOST_WHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDENROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREEN_YELLOW),
+    ("grey", GRAY),
+    ("honeydew", HONEYDEW),
+    ("hotpink", HOT_PINK),
+    ("indianred", INDIAN_RED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDER_BLUSH),
+    ("lawngreen", LAWN_GREEN),
+    ("lemonchiffon", LEMON_CHIFFON),
+    ("lightblue", LIGHT_BLUE),
+    ("lightcoral", LIGHT_CORAL),
+    ("lightcyan", LIGHT_CYAN),
+    ("lightgoldenrodyellow", LIGHT_GOLDENROD_YELLOW),
+    ("lightgray", LIGHT_GRAY),
+    ("lightgreen", LIGHT_GREEN),
+    ("lightgrey", LIGHT_GRAY),
+    ("lightpink", LIGHT_PINK),
+    ("lightsalmon", LIGHT_SALMON),
+    ("lightseagreen", LIGHT_SEA_GREEN),
+    ("lightskyblue", LIGHT_SKY_BLUE),
+    ("lightslategray", LIGHT_SLATE_GRAY),
+    ("lightslategrey", LIGHT_SLATE_GRAY),
+    ("lightsteelblue", LIGHT_STEEL_BLUE),
+    ("lightyellow", LIGHT_YELLOW),
+    ("lime", LIME),
+    ("limegreen", LIME_GREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUM_AQUAMARINE),
+    ("mediumblue", MEDIUM_BLUE),
+    ("mediumorchid", MEDIUM_ORCHID),
+    ("mediumpurple", MEDIUM_PURPLE),
+    ("mediumseagreen", MEDIUM_SEA_GREEN),
+    ("mediumslateblue", MEDIUM_SLATE_BLUE),
+    ("mediumspringgreen", MEDIUM_SPRING_GREEN),
+    ("mediumturquoise", MEDIUM_TURQUOISE),
+    ("mediumvioletred", MEDIUM_VIOLET_RED),
+    ("midnightblue", MIDNIGHT_BLUE),
+    ("mintcream", MINT_CREAM),
+    ("mistyrose", MISTY_ROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJO_WHITE),
+    ("navy", NAVY),
+    ("oldlace", OLD_LACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVE_DRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGE_RED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALE_GOLDENROD),
+    ("palegreen", PALE_GREEN),
+    ("paleturquoise", PALE_TURQUOISE),
+    ("palevioletred", PALE_VIOLET_RED),
+    ("papayawhip", PAPAYA_WHIP),
+    ("peachpuff", PEACH_PUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDER_BLUE),
+    ("purple", PURPLE),
+    ("rebeccapurple", REBECCA_PURPLE),
+    ("red", RED),
+    ("rosybrown", ROSY_BROWN),
+    ("royalblue", ROYAL_BLUE),
+    ("saddlebrown", SADDLE_BROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDY_BROWN),
+    ("seagreen", SEA_GREEN),
+    ("seashell", SEASHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKY_BLUE),
+    ("slateblue", SLATE_BLUE),
+    ("slategray", SLATE_GRAY),
+    ("slategrey", SLATE_GRAY),
+    ("snow", SNOW),
+    ("springgreen", SPRING_GREEN),
+    ("steelblue", STEEL_BLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("transparent", TRANSPARENT),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("webgreen", WEB_GREEN),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITE_SMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOW_GREEN),
+];
+
+/// The standard palette, which backends can allocate ahead of time.
+pub const STANDARD_PALETTE: &[Rgba] =
+    &[BLACK, WHITE, RED, GREEN, BLUE, YELLOW, CYAN, MAGENTA, GRAY];
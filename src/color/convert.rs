@@ -0,0 +1,269 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::Rgba;
+
+impl Rgba {
+    /// Parse a color from a hexadecimal string, in the form `#rgb`,
+    /// `#rgba`, `#rrggbb` or `#rrggbbaa`.
+    ///
+    /// The leading `#` is optional. Colors without an alpha component
+    /// are opaque.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        let digit = |i: usize| {
+            hex.as_bytes()
+                .get(i)
+                .and_then(|&c| (c as char).to_digit(16))
+                .map(|d| d as u8)
+        };
+        let short = |i: usize| digit(i).map(|d| d * 17);
+        let long = |i: usize| Some(digit(i)? * 16 + digit(i + 1)?);
+
+        match hex.len() {
+            3 => Some(Rgba::rgb(short(0)?, short(1)?, short(2)?)),
+            4 => Some(Rgba::new(short(0)?, short(1)?, short(2)?, short(3)?)),
+            6 => Some(Rgba::rgb(long(0)?, long(2)?, long(4)?)),
+            8 => Some(Rgba::new(long(0)?, long(2)?, long(4)?, long(6)?)),
+            _ => None,
+        }
+    }
+
+    /// Create a color from its hue, in degrees, and its saturation and
+    /// lightness, from 0 to 1.
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, a: u8) -> Self {
+        let saturation = clamp_unit(saturation);
+        let lightness = clamp_unit(lightness);
+        let chroma = (1.0 - abs(2.0 * lightness - 1.0)) * saturation;
+        from_hue_chroma(hue, chroma, lightness - chroma / 2.0, a)
+    }
+
+    /// Create a color from its hue, in degrees, and its saturation and
+    /// value, from 0 to 1.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, a: u8) -> Self {
+        let value = clamp_unit(value);
+        let chroma = value * clamp_unit(saturation);
+        from_hue_chroma(hue, chroma, value - chroma, a)
+    }
+
+    /// Get the hue, in degrees, and the saturation and lightness, from
+    /// 0 to 1, of this color.
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (hue, max, min) = hue_max_min(self);
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - abs(2.0 * lightness - 1.0))
+        };
+
+        (hue, saturation, lightness)
+    }
+
+    /// Get the hue, in degrees, and the saturation and value, from 0 to
+    /// 1, of this color.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (hue, max, min) = hue_max_min(self);
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    /// Mix this color with `other`, where a `t` of 0 gives this color
+    /// and a `t` of 1 gives `other`.
+    ///
+    /// The components are interpolated as they are stored, in sRGB. Use
+    /// `to_linear` and `from_linear` to mix in linear light instead.
+    pub fn lerp(self, other: Rgba, t: f32) -> Self {
+        let t = clamp_unit(t);
+        let mix = |a: u8, b: u8| unit_to_u8(a as f32 / 255.0 * (1.0 - t) + b as f32 / 255.0 * t);
+        Rgba::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+
+    cfg_std! {
+        /// Convert this color's components from sRGB to linear light,
+        /// from 0 to 1.
+        ///
+        /// The alpha component is already linear, so it is only scaled.
+        pub fn to_linear(self) -> [f32; 4] {
+            [
+                srgb_to_linear(self.r),
+                srgb_to_linear(self.g),
+                srgb_to_linear(self.b),
+                self.a as f32 / 255.0,
+            ]
+        }
+
+        /// Create a color from components in linear light, from 0 to 1.
+        pub fn from_linear(linear: [f32; 4]) -> Self {
+            Rgba::new(
+                linear_to_srgb(linear[0]),
+                linear_to_srgb(linear[1]),
+                linear_to_srgb(linear[2]),
+                unit_to_u8(linear[3]),
+            )
+        }
+
+        /// Get the relative luminance of this color, from 0 for black to
+        /// 1 for white, as defined by WCAG.
+        pub fn luminance(self) -> f32 {
+            let [r, g, b, _] = self.to_linear();
+            0.2126 * r + 0.7152 * g + 0.0722 * b
+        }
+
+        /// Get the contrast ratio between this color and `other`, from 1
+        /// for identical luminance to 21 for black on white, as defined
+        /// by WCAG.
+        pub fn contrast_ratio(self, other: Rgba) -> f32 {
+            let (a, b) = (self.luminance(), other.luminance());
+            let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+            (lighter + 0.05) / (darker + 0.05)
+        }
+    }
+}
+
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+fn clamp_unit(x: f32) -> f32 {
+    x.clamp(0.0, 1.0)
+}
+
+fn unit_to_u8(x: f32) -> u8 {
+    (clamp_unit(x) * 255.0 + 0.5) as u8
+}
+
+/// Create a color from its hue, chroma and the amount to add to each
+/// component to match its lightness.
+fn from_hue_chroma(hue: f32, chroma: f32, m: f32, a: u8) -> Rgba {
+    let hue = ((hue % 360.0) + 360.0) % 360.0 / 60.0;
+    let x = chroma * (1.0 - abs(hue % 2.0 - 1.0));
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    Rgba::new(unit_to_u8(r + m), unit_to_u8(g + m), unit_to_u8(b + m), a)
+}
+
+/// Get the hue of a color, along with its largest and smallest
+/// components.
+fn hue_max_min(color: Rgba) -> (f32, f32, f32) {
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * (((g - b) / delta) % 6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (if hue < 0.0 { hue + 360.0 } else { hue }, max, min)
+}
+
+cfg_std! {
+    fn srgb_to_linear(c: u8) -> f32 {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    fn linear_to_srgb(c: f32) -> u8 {
+        let c = clamp_unit(c);
+        let c = if c <= 0.0031308 {
+            c * 12.92
+        } else {
+            1.055 * c.powf(1.0 / 2.4) - 0.055
+        };
+        unit_to_u8(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::colors;
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!(Rgba::from_hex("#fff"), Some(Rgba::rgb(255, 255, 255)));
+        assert_eq!(Rgba::from_hex("0f08"), Some(Rgba::new(0, 255, 0, 136)));
+        assert_eq!(Rgba::from_hex("#1A2b3C"), Some(Rgba::rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(
+            Rgba::from_hex("#11223344"),
+            Some(Rgba::new(0x11, 0x22, 0x33, 0x44))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        for hex in [
+            "", "#", "#ff", "#fffff", "#ggg", "#12345g", "#+1+2+3", "##fff",
+        ] {
+            assert_eq!(Rgba::from_hex(hex), None, "{:?}", hex);
+        }
+    }
+
+    #[test]
+    fn converts_hsl() {
+        assert_eq!(Rgba::from_hsl(0.0, 1.0, 0.5, 255), Rgba::rgb(255, 0, 0));
+        assert_eq!(Rgba::from_hsl(120.0, 1.0, 0.25, 255), Rgba::rgb(0, 128, 0));
+        assert_eq!(Rgba::from_hsl(240.0, 1.0, 0.5, 7), Rgba::new(0, 0, 255, 7));
+        assert_eq!(Rgba::from_hsl(-120.0, 1.0, 0.5, 255), Rgba::rgb(0, 0, 255));
+        assert_eq!(
+            Rgba::from_hsl(42.0, 0.0, 1.0, 255),
+            Rgba::rgb(255, 255, 255)
+        );
+
+        let (hue, saturation, lightness) = Rgba::rgb(255, 0, 0).to_hsl();
+        assert_eq!((hue, saturation, lightness), (0.0, 1.0, 0.5));
+        let (_, saturation, _) = Rgba::rgb(128, 128, 128).to_hsl();
+        assert_eq!(saturation, 0.0);
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for color in [colors::ALICE_BLUE, colors::GREEN, Rgba::rgb(12, 200, 99)] {
+            let (hue, saturation, lightness) = color.to_hsl();
+            assert_eq!(Rgba::from_hsl(hue, saturation, lightness, 255), color);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Colors, and utilities for working with them.
+
+mod cache;
+pub use cache::ColorCache;
+
+pub mod colors;
+mod convert;
+
+/// A color with 8-bit red, green, blue and alpha components.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rgba {
+    /// The red component.
+    pub r: u8,
+    /// The green component.
+    pub g: u8,
+    /// The blue component.
+    pub b: u8,
+    /// The alpha component, where 0 is transparent and 255 is opaque.
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Create a new color from its components.
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Rgba { r, g, b, a }
+    }
+
+    /// Create a new, opaque color from its red, green and blue
+    /// components.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Rgba::new(r, g, b, 255)
+    }
+
+    /// Look up a color by its name, as listed in `colors::NAMED_COLORS`.
+    ///
+    /// Case and spaces are ignored, so X11-style names like
+    /// `"Alice Blue"` are found as well.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let key = name
+            .bytes()
+            .filter(|&b| b != b' ')
+            .map(|b| b.to_ascii_lowercase());
+
+        colors::NAMED_COLORS
+            .binary_search_by(|(named, _)| named.bytes().cmp(key.clone()))
+            .ok()
+            .map(|i| colors::NAMED_COLORS[i].1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_names() {
+        assert_eq!(Rgba::from_name("green"), Some(colors::GREEN));
+        assert_eq!(Rgba::from_name("green"), Some(Rgba::rgb(0, 128, 0)));
+        assert_eq!(Rgba::from_name("Alice Blue"), Some(colors::ALICE_BLUE));
+        assert_eq!(Rgba::from_name("LIME"), Some(Rgba::rgb(0, 255, 0)));
+        assert_eq!(Rgba::from_name("not a color"), None);
+    }
+
+    #[test]
+    fn named_colors_are_sorted() {
+        // from_name relies on this to binary search
+        for pair in colors::NAMED_COLORS.windows(2) {
+            assert!(pair[0].0 < pair[1].0, "{} >= {}", pair[0].0, pair[1].0);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::{Window, WindowProps};
+use alloc::{collections::BTreeSet, vec::Vec};
+use core::time::Duration;
+
+/// What the runtime does when its connection to the display server is
+/// lost, e.g. because the X server restarted.
+///
+/// Either way, an `EventType::DisplayDisconnected` event is delivered
+/// first.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ReconnectPolicy {
+    /// Give up: every later call fails with an `ErrorKind::Disconnected`
+    /// error.
+    #[default]
+    Never,
+    /// Try to connect again, and recreate every window from the
+    /// `WindowProps` it was created with, updated by the changes made
+    /// since.
+    ///
+    /// Windows keep their `Window` handles. Once they have been
+    /// recreated, an `EventType::DisplayReconnected` event is delivered,
+    /// followed by an exposure of each visible window.
+    Recreate {
+        /// How many times to try to connect before giving up.
+        attempts: u32,
+        /// How long to wait between attempts.
+        delay: Duration,
+    },
+}
+
+/// Order windows so that every window comes after its parent, which is
+/// the order they have to be recreated in after reconnecting.
+///
+/// Windows whose parent is not among `windows` are treated as top-level
+/// windows. This is intended for use by backends.
+pub fn recreation_order<'a, I>(windows: I) -> Vec<Window>
+where
+    I: IntoIterator<Item = (Window, &'a WindowProps)>,
+{
+    let windows: Vec<(Window, Option<Window>)> = windows
+        .into_iter()
+        .map(|(window, props)| (window, props.parent))
+        .collect();
+    let known: BTreeSet<Window> = windows.iter().map(|&(window, _)| window).collect();
+
+    let mut order = Vec::with_capacity(windows.len());
+    let mut placed = BTreeSet::new();
+    while order.len() < windows.len() {
+        let before = order.len();
+        for &(window, parent) in &windows {
+            let ready = match parent {
+                Some(parent) => !known.contains(&parent) || placed.contains(&parent),
+                None => true,
+            };
+            if ready && placed.insert(window) {
+                order.push(window);
+            }
+        }
+
+        // a cycle of parents can't be recreated
+        if order.len() == before {
+            break;
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::num::NonZeroUsize;
+
+    fn window(id: usize) -> Window {
+        Window::new(NonZeroUsize::new(id).unwrap())
+    }
+
+    fn child_of(parent: usize) -> WindowProps {
+        let mut props = WindowProps::new();
+        props.parent = Some(window(parent));
+        props
+    }
+
+    #[test]
+    fn parents_come_before_children() {
+        let top = WindowProps::new();
+        let (a, b) = (child_of(1), child_of(3));
+        // listed with the grandchild first
+        let windows = [(window(4), &b), (window(3), &a), (window(1), &top)];
+        assert_eq!(
+            recreation_order(windows.iter().cloned()),
+            [window(1), window(3), window(4)]
+        );
+    }
+
+    #[test]
+    fn unknown_parents_count_as_top_level() {
+        let orphan = child_of(9);
+        let windows = [(window(2), &orphan)];
+        assert_eq!(recreation_order(windows.iter().cloned()), [window(2)]);
+    }
+
+    #[test]
+    fn cycles_are_left_out() {
+        let (a, b) = (child_of(2), child_of(1));
+        let top = WindowProps::new();
+        let windows = [(window(1), &a), (window(2), &b), (window(3), &top)];
+        assert_eq!(recreation_order(windows.iter().cloned()), [window(3)]);
+    }
+}
@@ -0,0 +1,49 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+/// The kinds of native controls that a runtime can create.
+///
+/// Controls are child windows whose text is their title, so it can be
+/// changed with `Runtime::window_set_title` and read back with
+/// `Runtime::control_text`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ControlKind {
+    /// A push button, labelled with its text.
+    ///
+    /// This is a `BUTTON` control on Windows.
+    Button,
+    /// A single-line text box, which the user can edit.
+    ///
+    /// This is an `EDIT` control on Windows.
+    Edit,
+    /// A text label, which the user cannot edit.
+    ///
+    /// This is a `STATIC` control on Windows.
+    Label,
+}
+
+/// A notification from a native control, delivered to the control as
+/// `EventType::ControlCommand`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ControlCommand {
+    /// A button was clicked, e.g. `BN_CLICKED` on Windows.
+    Clicked,
+    /// The user changed the text of an edit control, e.g. `EN_CHANGE` on
+    /// Windows.
+    Changed,
+}
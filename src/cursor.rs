@@ -0,0 +1,167 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::Image;
+use alloc::collections::{btree_map::Entry, BTreeMap};
+use core::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The cursor displayed while the pointer is over a window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Cursor {
+    /// The platform's default cursor, usually an arrow.
+    Default,
+    /// A pointing hand, used for links.
+    Pointer,
+    /// An I-beam, used for text.
+    Text,
+    /// A crosshair.
+    Crosshair,
+    /// The application is busy and cannot be interacted with.
+    Wait,
+    /// The application is busy, but can still be interacted with.
+    Progress,
+    /// Something can be moved.
+    Move,
+    /// The action cannot be performed.
+    NotAllowed,
+    /// Something can be resized horizontally.
+    ResizeHorizontal,
+    /// Something can be resized vertically.
+    ResizeVertical,
+    /// Something can be resized from its top-left or bottom-right corner.
+    ResizeNwse,
+    /// Something can be resized from its top-right or bottom-left corner.
+    ResizeNesw,
+    /// No cursor is displayed.
+    Hidden,
+    /// A cursor created from an image.
+    Custom(CustomCursor),
+}
+
+/// A cursor created from an image.
+///
+/// Cloning a `CustomCursor` is cheap, and clones share the same
+/// identifier, so backends only need to create the native cursor once.
+#[derive(Clone)]
+pub struct CustomCursor {
+    id: usize,
+    image: Image,
+    hotspot: (u32, u32),
+}
+
+impl Cursor {
+    /// Create a cursor from an image, with the hotspot at the given
+    /// coordinates within the image.
+    pub fn from_image(image: &Image, hotspot: (u32, u32)) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        Cursor::Custom(CustomCursor {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            image: image.clone(),
+            hotspot,
+        })
+    }
+}
+
+impl CustomCursor {
+    /// Get the identifier of this cursor, which is unique among custom
+    /// cursors.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Get the image displayed by this cursor.
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Get the hotspot of this cursor.
+    pub fn hotspot(&self) -> (u32, u32) {
+        self.hotspot
+    }
+}
+
+impl fmt::Debug for CustomCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomCursor")
+            .field("id", &self.id)
+            .field("hotspot", &self.hotspot)
+            .finish()
+    }
+}
+
+impl PartialEq for CustomCursor {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomCursor {}
+
+impl core::hash::Hash for CustomCursor {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// A cache of native cursors, for use by backends.
+///
+/// Creating a native cursor from an image is expensive, so backends
+/// can keep the native handle here, keyed by the custom cursor's
+/// identifier.
+#[derive(Debug)]
+pub struct CursorCache<T> {
+    cursors: BTreeMap<usize, T>,
+}
+
+impl<T> Default for CursorCache<T> {
+    fn default() -> Self {
+        CursorCache {
+            cursors: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> CursorCache<T> {
+    /// Create a new, empty `CursorCache`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the native cursor for `cursor`, creating it with `create` if
+    /// it is not already cached.
+    pub fn get_or_create<E>(
+        &mut self,
+        cursor: &CustomCursor,
+        create: impl FnOnce(&CustomCursor) -> Result<T, E>,
+    ) -> Result<&T, E> {
+        match self.cursors.entry(cursor.id) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(create(cursor)?)),
+        }
+    }
+
+    /// Remove and return all of the cached native cursors, so that they
+    /// can be freed.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> {
+        core::mem::take(&mut self.cursors).into_values()
+    }
+}
@@ -0,0 +1,206 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Native message boxes and common file dialogs.
+//!
+//! On Linux and other Unix-likes, these are implemented by spawning
+//! `zenity`, which is available on most desktops. The functions here
+//! block until the user dismisses the dialog.
+
+use crate::Result;
+use std::path::PathBuf;
+
+/// The set of buttons to display in a message box.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageBoxButtons {
+    /// A single "OK" button.
+    Ok,
+    /// "OK" and "Cancel" buttons.
+    OkCancel,
+    /// "Yes" and "No" buttons.
+    YesNo,
+    /// "Yes", "No" and "Cancel" buttons.
+    YesNoCancel,
+}
+
+/// The button that the user pressed to dismiss a message box.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageBoxResponse {
+    /// The "OK" button.
+    Ok,
+    /// The "Cancel" button, or the dialog was closed.
+    Cancel,
+    /// The "Yes" button.
+    Yes,
+    /// The "No" button.
+    No,
+}
+
+/// A filter used to restrict the files shown in a file dialog.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FileFilter<'a> {
+    /// The human-readable name of the filter, e.g. "Images".
+    pub name: &'a str,
+    /// The glob patterns matched by this filter, e.g. `*.png`.
+    pub patterns: &'a [&'a str],
+}
+
+/// Display a message box and wait for the user to dismiss it.
+pub fn message_box(
+    title: &str,
+    text: &str,
+    buttons: MessageBoxButtons,
+) -> Result<MessageBoxResponse> {
+    imp::message_box(title, text, buttons)
+}
+
+/// Ask the user to select an existing file.
+///
+/// Returns `None` if the user cancelled the dialog.
+pub fn open_file(filters: &[FileFilter<'_>]) -> Result<Option<PathBuf>> {
+    imp::file_dialog(filters, false)
+}
+
+/// Ask the user to select a location to save a file.
+///
+/// Returns `None` if the user cancelled the dialog.
+pub fn save_file(filters: &[FileFilter<'_>]) -> Result<Option<PathBuf>> {
+    imp::file_dialog(filters, true)
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::{FileFilter, MessageBoxButtons, MessageBoxResponse};
+    use crate::{Error, Result};
+    use alloc::string::String;
+    use std::{path::PathBuf, process::Command};
+
+    pub(super) fn message_box(
+        title: &str,
+        text: &str,
+        buttons: MessageBoxButtons,
+    ) -> Result<MessageBoxResponse> {
+        let mut cmd = Command::new("zenity");
+        // the text is plain, not Pango markup
+        cmd.arg("--title")
+            .arg(title)
+            .arg("--text")
+            .arg(text)
+            .arg("--no-markup");
+
+        match buttons {
+            MessageBoxButtons::Ok => {
+                cmd.arg("--info");
+            }
+            MessageBoxButtons::OkCancel => {
+                cmd.args(["--question", "--ok-label", "OK", "--cancel-label", "Cancel"]);
+            }
+            MessageBoxButtons::YesNo | MessageBoxButtons::YesNoCancel => {
+                cmd.args(["--question", "--ok-label", "Yes", "--cancel-label", "No"]);
+                if buttons == MessageBoxButtons::YesNoCancel {
+                    cmd.args(["--extra-button", "Cancel"]);
+                }
+            }
+        }
+
+        tracing::trace!("Spawning zenity for message box");
+        let output = cmd.output()?;
+
+        // zenity exits with 0 for the affirmative button and 1 for the
+        // negative one, printing the label of any extra button pressed;
+        // anything else means that it failed
+        let affirmative = match output.status.code() {
+            Some(0) => true,
+            Some(1) => false,
+            _ => {
+                tracing::error!("zenity exited unexpectedly: {:?}", output.status);
+                return Err(Error::backend("zenity", output.status));
+            }
+        };
+
+        let response = match (buttons, affirmative) {
+            (MessageBoxButtons::Ok, _) => MessageBoxResponse::Ok,
+            (MessageBoxButtons::OkCancel, true) => MessageBoxResponse::Ok,
+            (MessageBoxButtons::OkCancel, false) => MessageBoxResponse::Cancel,
+            (_, true) => MessageBoxResponse::Yes,
+            (_, false) if output.stdout.is_empty() => MessageBoxResponse::No,
+            (_, false) => MessageBoxResponse::Cancel,
+        };
+
+        Ok(response)
+    }
+
+    pub(super) fn file_dialog(filters: &[FileFilter<'_>], save: bool) -> Result<Option<PathBuf>> {
+        let mut cmd = Command::new("zenity");
+        cmd.arg("--file-selection");
+
+        if save {
+            cmd.arg("--save");
+        }
+
+        for filter in filters {
+            cmd.arg("--file-filter").arg(filter_arg(filter));
+        }
+
+        tracing::trace!("Spawning zenity for file dialog");
+        let output = cmd.output()?;
+
+        match output.status.code() {
+            Some(0) => {
+                let mut path = String::from_utf8(output.stdout)
+                    .map_err(|_| Error::unsupported("non-UTF-8 file paths"))?;
+                let trimmed = path.trim_end_matches('\n').len();
+                path.truncate(trimmed);
+                Ok(Some(PathBuf::from(path)))
+            }
+            Some(1) => Ok(None),
+            _ => {
+                tracing::error!("zenity exited unexpectedly: {:?}", output.status);
+                Err(Error::backend("zenity", output.status))
+            }
+        }
+    }
+
+    fn filter_arg(filter: &FileFilter<'_>) -> String {
+        let mut arg = String::from(filter.name);
+        arg.push_str(" |");
+        for pattern in filter.patterns {
+            arg.push(' ');
+            arg.push_str(pattern);
+        }
+        arg
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use super::{FileFilter, MessageBoxButtons, MessageBoxResponse};
+    use crate::{Error, Result};
+    use std::path::PathBuf;
+
+    pub(super) fn message_box(
+        _title: &str,
+        _text: &str,
+        _buttons: MessageBoxButtons,
+    ) -> Result<MessageBoxResponse> {
+        Err(Error::unsupported("message_box"))
+    }
+
+    pub(super) fn file_dialog(_filters: &[FileFilter<'_>], _save: bool) -> Result<Option<PathBuf>> {
+        Err(Error::unsupported("file dialogs"))
+    }
+}
@@ -0,0 +1,156 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+/// The level of DPI awareness a process opts into.
+///
+/// Platforms that scale the output of DPI-unaware applications, like
+/// Windows, produce blurry windows unless the process declares that it
+/// handles scaling itself. Backends perform this opt-in at runtime, so
+/// that no application manifest is required.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DpiAwareness {
+    /// The application is not DPI aware, and is scaled by the system.
+    Unaware,
+    /// The application is aware of the DPI of the primary monitor
+    /// when it started.
+    System,
+    /// The application is aware of the DPI of each monitor.
+    PerMonitor,
+    /// The application is aware of the DPI of each monitor, and the
+    /// non-client area of its windows (title bars, borders, scroll bars)
+    /// is scaled by the system as well.
+    ///
+    /// This is the default.
+    #[default]
+    PerMonitorV2,
+}
+
+/// The DPI at which one logical pixel is one physical pixel.
+pub const BASE_DPI: u32 = 96;
+
+/// A size in logical pixels, which are scaled by the monitor's scale
+/// factor to give physical pixels.
+///
+/// Laying out in logical pixels keeps an interface the same apparent
+/// size on monitors of different densities.
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct LogicalSize {
+    /// The width, in logical pixels.
+    pub width: f64,
+    /// The height, in logical pixels.
+    pub height: f64,
+}
+
+/// A size in physical pixels, as used by the windowing system.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PhysicalSize {
+    /// The width, in physical pixels.
+    pub width: u32,
+    /// The height, in physical pixels.
+    pub height: u32,
+}
+
+/// A position in logical pixels.
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct LogicalPosition {
+    /// The X coordinate, in logical pixels.
+    pub x: f64,
+    /// The Y coordinate, in logical pixels.
+    pub y: f64,
+}
+
+/// A position in physical pixels.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PhysicalPosition {
+    /// The X coordinate, in physical pixels.
+    pub x: i32,
+    /// The Y coordinate, in physical pixels.
+    pub y: i32,
+}
+
+impl LogicalSize {
+    /// Create a new `LogicalSize`.
+    pub fn new(width: f64, height: f64) -> Self {
+        LogicalSize { width, height }
+    }
+
+    /// Convert this size to physical pixels, rounding to the nearest
+    /// pixel.
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalSize {
+        PhysicalSize::new(
+            round(self.width * scale_factor).max(0) as u32,
+            round(self.height * scale_factor).max(0) as u32,
+        )
+    }
+}
+
+impl PhysicalSize {
+    /// Create a new `PhysicalSize`.
+    pub fn new(width: u32, height: u32) -> Self {
+        PhysicalSize { width, height }
+    }
+
+    /// Convert this size to logical pixels.
+    pub fn to_logical(self, scale_factor: f64) -> LogicalSize {
+        LogicalSize::new(
+            f64::from(self.width) / scale_factor,
+            f64::from(self.height) / scale_factor,
+        )
+    }
+}
+
+impl LogicalPosition {
+    /// Create a new `LogicalPosition`.
+    pub fn new(x: f64, y: f64) -> Self {
+        LogicalPosition { x, y }
+    }
+
+    /// Convert this position to physical pixels, rounding to the nearest
+    /// pixel.
+    pub fn to_physical(self, scale_factor: f64) -> PhysicalPosition {
+        PhysicalPosition::new(
+            round(self.x * scale_factor) as i32,
+            round(self.y * scale_factor) as i32,
+        )
+    }
+}
+
+impl PhysicalPosition {
+    /// Create a new `PhysicalPosition`.
+    pub fn new(x: i32, y: i32) -> Self {
+        PhysicalPosition { x, y }
+    }
+
+    /// Convert this position to logical pixels.
+    pub fn to_logical(self, scale_factor: f64) -> LogicalPosition {
+        LogicalPosition::new(
+            f64::from(self.x) / scale_factor,
+            f64::from(self.y) / scale_factor,
+        )
+    }
+}
+
+/// Round to the nearest integer, away from zero at halves.
+///
+/// `f64::round` needs the standard library.
+fn round(x: f64) -> i64 {
+    if x < 0.0 {
+        (x - 0.5) as i64
+    } else {
+        (x + 0.5) as i64
+    }
+}
@@ -15,7 +15,8 @@
 // Public License along with gui-tools. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use alloc::boxed::Box;
+use crate::Window;
+use alloc::{boxed::Box, string::String};
 use chalkboard::Error as ChalkError;
 use core::fmt;
 
@@ -23,16 +24,241 @@ use core::fmt;
 pub struct Error {
     /// The inner internal error.
     error: Internal,
+    /// The window that the error concerns, if any.
+    window: Option<Window>,
+    /// The screen that the error concerns, if any.
+    screen: Option<usize>,
     /// A chain to a previous error, if this error exists in a list
     /// of consequential errors.
     link: Option<Box<Error>>,
 }
 
+/// The general category of an `Error`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An error described only by its message.
+    Message,
+    /// The operation is not supported on this platform or backend.
+    Unsupported,
+    /// An argument passed to the operation is not valid.
+    InvalidArgument,
+    /// The window does not belong to this runtime.
+    NotOurWindow,
+    /// The requested screen does not exist.
+    NoScreen,
+    /// The backend's underlying windowing system reported an error.
+    Backend,
+    /// The resource is already in use.
+    Conflict,
+    /// An error occurred while drawing.
+    Drawing,
+    /// An I/O error occurred.
+    Io,
+    /// The connection to the display server was lost.
+    Disconnected,
+}
+
 enum Internal {
     /// A chalkboard error.
     ///
     /// This type is also used to store string-based message errors.
     Chalk(ChalkError),
+    /// A static string message.
+    StaticMsg(&'static str),
+    /// A formatted string message.
+    Msg(String),
+    /// The operation is not supported on this platform or backend.
+    Unsupported(&'static str),
+    /// An argument passed to the operation is not valid.
+    InvalidArgument(String),
+    /// The window does not belong to this runtime.
+    NotOurWindow,
+    /// The requested screen does not exist.
+    NoScreen,
+    /// The given resource is already in use.
+    Conflict(&'static str),
+    /// An error from the backend's windowing system, e.g. an X11 or
+    /// Win32 error.
+    Backend(&'static str, Box<dyn BackendError>),
+    /// An error from the backend's windowing system that can be
+    /// reported as the `source()` of this error.
+    #[cfg(feature = "std")]
+    BackendSource(&'static str, Box<dyn std::error::Error + Send + Sync>),
+    /// The connection to the display server was lost.
+    Disconnected,
+    /// An I/O error occurred.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+/// Errors that can be reported by backends.
+trait BackendError: fmt::Debug + fmt::Display + Send + Sync {}
+
+impl<T: fmt::Debug + fmt::Display + Send + Sync> BackendError for T {}
+
+impl Error {
+    /// Create an error from a static string message.
+    pub fn static_msg(msg: &'static str) -> Self {
+        Internal::StaticMsg(msg).into()
+    }
+
+    /// Create an error from a formatted message.
+    pub fn msg(msg: impl Into<String>) -> Self {
+        Internal::Msg(msg.into()).into()
+    }
+
+    /// Create an error indicating that an operation is not supported.
+    pub fn unsupported(operation: &'static str) -> Self {
+        Internal::Unsupported(operation).into()
+    }
+
+    /// Create an error indicating that an argument is not valid.
+    pub fn invalid_argument(msg: impl Into<String>) -> Self {
+        Internal::InvalidArgument(msg.into()).into()
+    }
+
+    /// Create an error indicating that a window does not belong to this
+    /// runtime.
+    pub fn not_our_window(window: Window) -> Self {
+        Error::from(Internal::NotOurWindow).with_window(window)
+    }
+
+    /// Create an error indicating that a null window ID was used.
+    pub(crate) fn null_window() -> Self {
+        Internal::NotOurWindow.into()
+    }
+
+    /// Create an error indicating that a screen does not exist.
+    pub fn no_screen(screen: usize) -> Self {
+        Error::from(Internal::NoScreen).with_screen(screen)
+    }
+
+    /// Create an error indicating that a resource, such as a keyboard
+    /// shortcut, is already in use.
+    pub fn conflict(resource: &'static str) -> Self {
+        Internal::Conflict(resource).into()
+    }
+
+    /// Create an error indicating that the connection to the display
+    /// server was lost.
+    pub fn disconnected() -> Self {
+        Internal::Disconnected.into()
+    }
+
+    /// Create an error from an error reported by a backend's windowing
+    /// system.
+    pub fn backend(
+        backend: &'static str,
+        error: impl fmt::Debug + fmt::Display + Send + Sync + 'static,
+    ) -> Self {
+        Internal::Backend(backend, Box::new(error)).into()
+    }
+
+    cfg_std! {
+        /// Create an error from an error reported by a backend's windowing
+        /// system, keeping it as the `source()` of the new error.
+        pub fn backend_source(
+            backend: &'static str,
+            error: impl std::error::Error + Send + Sync + 'static,
+        ) -> Self {
+            Internal::BackendSource(backend, Box::new(error)).into()
+        }
+    }
+
+    /// Get the general category of this error.
+    pub fn kind(&self) -> ErrorKind {
+        match self.error {
+            Internal::Chalk(_) => ErrorKind::Drawing,
+            Internal::StaticMsg(_) | Internal::Msg(_) => ErrorKind::Message,
+            Internal::Unsupported(_) => ErrorKind::Unsupported,
+            Internal::InvalidArgument(_) => ErrorKind::InvalidArgument,
+            Internal::NotOurWindow => ErrorKind::NotOurWindow,
+            Internal::NoScreen => ErrorKind::NoScreen,
+            Internal::Conflict(_) => ErrorKind::Conflict,
+            Internal::Backend(..) => ErrorKind::Backend,
+            #[cfg(feature = "std")]
+            Internal::BackendSource(..) => ErrorKind::Backend,
+            Internal::Disconnected => ErrorKind::Disconnected,
+            #[cfg(feature = "std")]
+            Internal::Io(_) => ErrorKind::Io,
+        }
+    }
+
+    /// Get the window that this error concerns, if any.
+    pub fn window(&self) -> Option<Window> {
+        self.window
+    }
+
+    /// Get the screen that this error concerns, if any.
+    pub fn screen(&self) -> Option<usize> {
+        self.screen
+    }
+
+    /// Get the previous error in the chain, if any.
+    ///
+    /// This walks the list of consequential errors; the error that
+    /// caused this one, if any, is its `source()`.
+    pub fn next(&self) -> Option<&Error> {
+        self.link.as_deref()
+    }
+
+    /// Note that this error concerns the given window.
+    pub fn with_window(mut self, window: Window) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Note that this error concerns the given screen.
+    pub fn with_screen(mut self, screen: usize) -> Self {
+        self.screen = Some(screen);
+        self
+    }
+
+    /// Add a previous error to the end of this error's chain.
+    pub fn chain(mut self, previous: Error) -> Self {
+        let mut slot = &mut self.link;
+        while let Some(link) = slot {
+            slot = &mut link.link;
+        }
+        *slot = Some(Box::new(previous));
+        self
+    }
+}
+
+impl From<Internal> for Error {
+    fn from(error: Internal) -> Self {
+        Error {
+            error,
+            window: None,
+            screen: None,
+            link: None,
+        }
+    }
+}
+
+impl From<ChalkError> for Error {
+    fn from(error: ChalkError) -> Self {
+        Internal::Chalk(error).into()
+    }
+}
+
+cfg_std! {
+    impl From<std::io::Error> for Error {
+        fn from(error: std::io::Error) -> Self {
+            Internal::Io(error).into()
+        }
+    }
+
+    impl std::error::Error for Error {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self.error {
+                Internal::Io(ref e) => Some(e),
+                Internal::BackendSource(_, ref e) => Some(&**e),
+                _ => None,
+            }
+        }
+    }
 }
 
 impl fmt::Debug for Error {
@@ -40,6 +266,14 @@ impl fmt::Debug for Error {
         let mut dt = f.debug_tuple("Error");
         dt.field(&self.error);
 
+        if let Some(ref window) = self.window {
+            dt.field(window);
+        }
+
+        if let Some(ref screen) = self.screen {
+            dt.field(screen);
+        }
+
         if let Some(ref link) = self.link {
             dt.field(link);
         }
@@ -52,6 +286,19 @@ impl fmt::Debug for Internal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Chalk(c) => fmt::Debug::fmt(c, f),
+            Self::StaticMsg(msg) => fmt::Debug::fmt(msg, f),
+            Self::Msg(msg) => fmt::Debug::fmt(msg, f),
+            Self::Unsupported(op) => f.debug_tuple("Unsupported").field(op).finish(),
+            Self::InvalidArgument(msg) => f.debug_tuple("InvalidArgument").field(msg).finish(),
+            Self::NotOurWindow => f.write_str("NotOurWindow"),
+            Self::NoScreen => f.write_str("NoScreen"),
+            Self::Conflict(res) => f.debug_tuple("Conflict").field(res).finish(),
+            Self::Backend(name, e) => f.debug_tuple("Backend").field(name).field(e).finish(),
+            #[cfg(feature = "std")]
+            Self::BackendSource(name, e) => f.debug_tuple("Backend").field(name).field(e).finish(),
+            Self::Disconnected => f.write_str("Disconnected"),
+            #[cfg(feature = "std")]
+            Self::Io(e) => fmt::Debug::fmt(e, f),
         }
     }
 }
@@ -61,6 +308,28 @@ impl fmt::Display for Error {
         // write the initial error message
         match self.error {
             Internal::Chalk(ref e) => fmt::Display::fmt(e, f)?,
+            Internal::StaticMsg(msg) => f.write_str(msg)?,
+            Internal::Msg(ref msg) => f.write_str(msg)?,
+            Internal::Unsupported(op) => write!(f, "Operation is not supported: {}", op)?,
+            Internal::InvalidArgument(ref msg) => write!(f, "Invalid argument: {}", msg)?,
+            Internal::NotOurWindow => f.write_str("Window does not belong to this runtime")?,
+            Internal::NoScreen => f.write_str("Screen does not exist")?,
+            Internal::Conflict(res) => write!(f, "{} is already in use", res)?,
+            Internal::Backend(name, ref e) => write!(f, "{} error: {}", name, e)?,
+            #[cfg(feature = "std")]
+            Internal::BackendSource(name, ref e) => write!(f, "{} error: {}", name, e)?,
+            Internal::Disconnected => f.write_str("Lost the connection to the display server")?,
+            #[cfg(feature = "std")]
+            Internal::Io(ref e) => fmt::Display::fmt(e, f)?,
+        }
+
+        // write the context, if any
+        if let Some(window) = self.window {
+            write!(f, " (window {})", window.id())?;
+        }
+
+        if let Some(screen) = self.screen {
+            write!(f, " (screen {})", screen)?;
         }
 
         // if necessary, write the link to the previous error
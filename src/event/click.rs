@@ -0,0 +1,185 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use super::{Event, EventType, MouseButton};
+use crate::Window;
+use core::time::Duration;
+
+/// The double-click interval used when the system does not provide one.
+pub const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How far, in pixels, the pointer may move between the clicks of a
+/// double click.
+const DEFAULT_DOUBLE_CLICK_DISTANCE: u32 = 4;
+
+/// Recognizes double and triple clicks in a stream of
+/// `EventType::MouseButtonDown` events.
+///
+/// This is intended for use by backends, which feed it every event as it
+/// is queued, with the system's double-click interval
+/// (`GetDoubleClickTime` on Windows). Events need timestamps for clicks
+/// to be recognized.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClickTracker {
+    interval: Duration,
+    distance: u32,
+    last: Option<Click>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Click {
+    window: Option<Window>,
+    button: MouseButton,
+    x: i32,
+    y: i32,
+    time: Duration,
+    count: u32,
+}
+
+impl Default for ClickTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_DOUBLE_CLICK_INTERVAL)
+    }
+}
+
+impl ClickTracker {
+    /// Create a new `ClickTracker`, which counts presses less than
+    /// `interval` apart as part of the same click.
+    pub fn new(interval: Duration) -> Self {
+        ClickTracker {
+            interval,
+            distance: DEFAULT_DOUBLE_CLICK_DISTANCE,
+            last: None,
+        }
+    }
+
+    /// Get the double-click interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Set the double-click interval.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Set how far, in pixels, the pointer may move between clicks.
+    pub fn set_distance(&mut self, distance: u32) {
+        self.distance = distance;
+    }
+
+    /// Feed an event to the tracker, returning the double or triple
+    /// click event that should be queued after it, if any.
+    ///
+    /// A fourth click starts counting again from one.
+    pub fn track(&mut self, event: &Event) -> Option<Event> {
+        let (x, y, button) = match *event.ty() {
+            EventType::MouseButtonDown { x, y, button } => (x, y, button),
+            _ => return None,
+        };
+        let time = event.timestamp()?;
+
+        let count = match self.last {
+            Some(last)
+                if last.window == event.window()
+                    && last.button == button
+                    && last.count < 3
+                    && time >= last.time
+                    && time - last.time <= self.interval
+                    && x.abs_diff(last.x) <= self.distance
+                    && y.abs_diff(last.y) <= self.distance =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+
+        self.last = Some(Click {
+            window: event.window(),
+            button,
+            x,
+            y,
+            time,
+            count,
+        });
+
+        let ty = match count {
+            2 => EventType::MouseDoubleClick { x, y, button },
+            3 => EventType::MouseTripleClick { x, y, button },
+            _ => return None,
+        };
+        Some(Event::new(event.window(), ty).with_timestamp(time))
+    }
+
+    /// Forget the previous click, e.g. because the pointer left the
+    /// window.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use core::num::NonZeroUsize;
+
+    fn press(x: i32, millis: u64) -> Event {
+        let window = Window::new(NonZeroUsize::new(1).unwrap());
+        let button = MouseButton::Left;
+        Event::new(Some(window), EventType::MouseButtonDown { x, y: 0, button })
+            .with_timestamp(Duration::from_millis(millis))
+    }
+
+    /// Feed presses to a tracker, returning the click counts it reports.
+    fn counts(presses: &[Event]) -> Vec<u32> {
+        let mut tracker = ClickTracker::default();
+        presses
+            .iter()
+            .map(|event| match tracker.track(event).as_ref().map(Event::ty) {
+                Some(EventType::MouseDoubleClick { .. }) => 2,
+                Some(EventType::MouseTripleClick { .. }) => 3,
+                _ => 1,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counts_up_to_three_clicks() {
+        let presses = [press(0, 0), press(0, 100), press(0, 200), press(0, 300)];
+        assert_eq!(counts(&presses), [1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn slow_presses_are_separate_clicks() {
+        let presses = [press(0, 0), press(0, 501), press(0, 1001)];
+        assert_eq!(counts(&presses), [1, 1, 2]);
+    }
+
+    #[test]
+    fn distant_presses_are_separate_clicks() {
+        let presses = [press(0, 0), press(5, 100), press(9, 200)];
+        assert_eq!(counts(&presses), [1, 1, 2]);
+    }
+
+    #[test]
+    fn ignores_presses_without_timestamps() {
+        let mut tracker = ClickTracker::default();
+        let untimed = Event::new(None, press(0, 0).ty().clone());
+        assert!(tracker.track(&untimed).is_none());
+        assert!(tracker.track(&untimed).is_none());
+    }
+}
@@ -0,0 +1,87 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use super::{Event, EventType};
+use crate::Window;
+use alloc::vec::Vec;
+
+/// Turns the window under the pointer into a consistent sequence of
+/// `EventType::MouseEntered` and `EventType::MouseLeft` events.
+///
+/// X11 reports crossings between a window and its children with
+/// different details, while Windows only reports leaving a window after
+/// `TrackMouseEvent` is armed. Backends instead tell this tracker which
+/// window is under the pointer, and deliver the events it returns, so
+/// that every backend follows the same rules:
+///
+/// - A window is entered when the pointer moves over it or any of its
+///   descendants, and left when the pointer is over neither.
+/// - Leaves are delivered before enters, innermost window first, and
+///   enters are delivered outermost window first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CrossingTracker {
+    /// The windows the pointer is in, outermost first.
+    path: Vec<Window>,
+}
+
+impl CrossingTracker {
+    /// Create a new `CrossingTracker`, with the pointer outside every
+    /// window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the innermost window the pointer is over, if any.
+    pub fn current(&self) -> Option<Window> {
+        self.path.last().copied()
+    }
+
+    /// Note that the pointer is now over the last window of `path`,
+    /// whose ancestors are listed before it, outermost first.
+    ///
+    /// An empty `path` means that the pointer left every window.
+    pub fn update(&mut self, path: &[Window]) -> Vec<Event> {
+        let common = self
+            .path
+            .iter()
+            .zip(path)
+            .take_while(|(old, new)| old == new)
+            .count();
+        let mut events = Vec::new();
+
+        for &window in self.path[common..].iter().rev() {
+            events.push(Event::new(Some(window), EventType::MouseLeft));
+        }
+
+        if let Some(&child) = path.last() {
+            for &window in &path[common..] {
+                events.push(Event::new(Some(window), EventType::MouseEntered { child }));
+            }
+        }
+
+        self.path = path.to_vec();
+        events
+    }
+
+    /// Forget about a window, e.g. because it was destroyed.
+    ///
+    /// The pointer is taken to be over the window's parent instead.
+    pub fn remove(&mut self, window: Window) {
+        if let Some(index) = self.path.iter().position(|&w| w == window) {
+            self.path.truncate(index);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{Event, EventQueue, QueuePolicy};
+use crate::{Error, Result};
+use alloc::{boxed::Box, collections::VecDeque};
+use core::fmt;
+
+/// The strategy a runtime uses to hold events between receiving them
+/// from the windowing system and handing them to `next_event`.
+///
+/// `EventQueue` is the default, but a priority queue or any other
+/// implementation can be chosen with `RuntimeBuilder::delivery`, which
+/// backends consult through `RuntimeBuilder::event_delivery`.
+pub trait EventDelivery: fmt::Debug + Send {
+    /// Add an event to be delivered.
+    fn push(&mut self, event: Event);
+
+    /// Take the next event to be delivered.
+    fn pop(&mut self) -> Option<Event>;
+
+    /// Get the number of events waiting to be delivered.
+    fn len(&self) -> usize;
+
+    /// Tell whether no events are waiting to be delivered.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Discard every event waiting to be delivered.
+    fn clear(&mut self);
+
+    /// Set the policy describing how floods of events are handled.
+    ///
+    /// Deliveries that do not support policies report an error.
+    #[allow(unused_variables)]
+    fn set_policy(&mut self, policy: QueuePolicy) -> Result {
+        Err(Error::unsupported("set_queue_policy"))
+    }
+}
+
+impl EventDelivery for EventQueue {
+    fn push(&mut self, event: Event) {
+        EventQueue::push(self, event)
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        EventQueue::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        EventQueue::len(self)
+    }
+
+    fn clear(&mut self) {
+        EventQueue::clear(self)
+    }
+
+    fn set_policy(&mut self, policy: QueuePolicy) -> Result {
+        EventQueue::set_policy(self, policy);
+        Ok(())
+    }
+}
+
+/// A plain first-in, first-out delivery, with no policy.
+impl EventDelivery for VecDeque<Event> {
+    fn push(&mut self, event: Event) {
+        self.push_back(event)
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        self.pop_front()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+}
+
+impl<D: EventDelivery + ?Sized> EventDelivery for Box<D> {
+    fn push(&mut self, event: Event) {
+        (**self).push(event)
+    }
+
+    fn pop(&mut self) -> Option<Event> {
+        (**self).pop()
+    }
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn clear(&mut self) {
+        (**self).clear()
+    }
+
+    fn set_policy(&mut self, policy: QueuePolicy) -> Result {
+        (**self).set_policy(policy)
+    }
+}
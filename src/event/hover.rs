@@ -0,0 +1,237 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use super::{Event, EventType, MouseButton};
+use crate::Window;
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::time::Duration;
+
+/// How far, in pixels, the pointer may drift without restarting a hover
+/// or cancelling a long press.
+const SLOP: u32 = 4;
+
+/// When a window receives `EventType::MouseHover` and
+/// `EventType::LongPress` events.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct HoverConfig {
+    /// How long the pointer needs to rest before a hover is reported, or
+    /// `None` to never report hovers.
+    pub hover_delay: Option<Duration>,
+    /// How long a button or finger needs to be held down in place before
+    /// a long press is reported, or `None` to never report long presses.
+    pub long_press_delay: Option<Duration>,
+}
+
+impl HoverConfig {
+    /// Create a new `HoverConfig` that reports nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report hovers after the pointer rests for `delay`.
+    pub fn with_hover_delay(mut self, delay: Duration) -> Self {
+        self.hover_delay = Some(delay);
+        self
+    }
+
+    /// Report long presses after a button is held for `delay`.
+    pub fn with_long_press_delay(mut self, delay: Duration) -> Self {
+        self.long_press_delay = Some(delay);
+        self
+    }
+}
+
+/// Where the pointer came to rest, or where a button was pressed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Rest {
+    x: i32,
+    y: i32,
+    since: Duration,
+    fired: bool,
+}
+
+impl Rest {
+    fn new(x: i32, y: i32, since: Duration) -> Self {
+        Rest {
+            x,
+            y,
+            since,
+            fired: false,
+        }
+    }
+
+    fn near(&self, x: i32, y: i32) -> bool {
+        x.abs_diff(self.x) <= SLOP && y.abs_diff(self.y) <= SLOP
+    }
+
+    /// Tell whether this rest has lasted for `delay` by `now`, marking
+    /// it as reported if it has.
+    fn fire(&mut self, delay: Option<Duration>, now: Duration) -> bool {
+        match self.deadline(delay) {
+            Some(deadline) if now >= deadline => {
+                self.fired = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get when this rest will have lasted for `delay`, if it has not
+    /// been reported yet.
+    ///
+    /// A deadline too far off to represent never comes.
+    fn deadline(&self, delay: Option<Duration>) -> Option<Duration> {
+        match delay {
+            Some(delay) if !self.fired => self.since.checked_add(delay),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct WindowHover {
+    config: HoverConfig,
+    pointer: Option<Rest>,
+    press: Option<(MouseButton, Rest)>,
+}
+
+/// Synthesizes `EventType::MouseHover` and `EventType::LongPress` events
+/// from timestamped pointer events.
+///
+/// This is intended for use by backends. They feed it every event as it
+/// is queued, arm a timer for `next_deadline`, and call `advance` when
+/// the timer fires.
+#[derive(Debug, Clone, Default)]
+pub struct HoverTracker {
+    windows: BTreeMap<Window, WindowHover>,
+}
+
+impl HoverTracker {
+    /// Create a new `HoverTracker`, which reports nothing until windows
+    /// are configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set when a window receives hover and long-press events.
+    pub fn set_config(&mut self, window: Window, config: HoverConfig) {
+        if config == HoverConfig::default() {
+            self.windows.remove(&window);
+        } else {
+            self.windows.entry(window).or_default().config = config;
+        }
+    }
+
+    /// Forget about a window, e.g. because it was destroyed.
+    pub fn remove(&mut self, window: Window) {
+        self.windows.remove(&window);
+    }
+
+    /// Feed an event to the tracker.
+    ///
+    /// Events without a window or a timestamp are ignored.
+    pub fn track(&mut self, event: &Event) {
+        let (window, now) = match (event.window(), event.timestamp()) {
+            (Some(window), Some(now)) => (window, now),
+            _ => return,
+        };
+        let state = match self.windows.get_mut(&window) {
+            Some(state) => state,
+            None => return,
+        };
+
+        match *event.ty() {
+            EventType::MouseMoved { x, y } => {
+                match state.pointer {
+                    Some(ref rest) if rest.near(x, y) => {}
+                    _ => state.pointer = Some(Rest::new(x, y, now)),
+                }
+
+                if let Some((_, ref rest)) = state.press {
+                    if !rest.near(x, y) {
+                        state.press = None;
+                    }
+                }
+            }
+            EventType::MouseButtonDown { x, y, button } => {
+                // pressing a button is not hovering
+                if let Some(ref mut rest) = state.pointer {
+                    rest.fired = true;
+                }
+                state.press = Some((button, Rest::new(x, y, now)));
+            }
+            EventType::MouseButtonUp { button, .. } => {
+                if matches!(state.press, Some((pressed, _)) if pressed == button) {
+                    state.press = None;
+                }
+            }
+            EventType::MouseLeft => {
+                // the pointer can't rest somewhere it isn't
+                state.pointer = None;
+                state.press = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Get the earliest time at which `advance` may produce an event.
+    pub fn next_deadline(&self) -> Option<Duration> {
+        self.windows
+            .values()
+            .flat_map(|state| {
+                let hover = state
+                    .pointer
+                    .and_then(|rest| rest.deadline(state.config.hover_delay));
+                let press = state
+                    .press
+                    .and_then(|(_, rest)| rest.deadline(state.config.long_press_delay));
+                hover.into_iter().chain(press)
+            })
+            .min()
+    }
+
+    /// Produce the hover and long-press events that are due by `now`.
+    pub fn advance(&mut self, now: Duration) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        for (&window, state) in &mut self.windows {
+            if let Some(ref mut rest) = state.pointer {
+                if rest.fire(state.config.hover_delay, now) {
+                    let ty = EventType::MouseHover {
+                        x: rest.x,
+                        y: rest.y,
+                        duration: now - rest.since,
+                    };
+                    events.push(Event::new(Some(window), ty).with_timestamp(now));
+                }
+            }
+
+            if let Some((button, ref mut rest)) = state.press {
+                if rest.fire(state.config.long_press_delay, now) {
+                    let ty = EventType::LongPress {
+                        x: rest.x,
+                        y: rest.y,
+                        button,
+                    };
+                    events.push(Event::new(Some(window), ty).with_timestamp(now));
+                }
+            }
+        }
+
+        events
+    }
+}
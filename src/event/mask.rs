@@ -0,0 +1,128 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use super::EventType;
+use core::ops::{BitOr, BitOrAssign};
+
+/// A set of event categories that a window receives.
+///
+/// Events outside of the mask are never delivered to the window, and
+/// backends avoid selecting for them at all where they can. This matters
+/// most for `POINTER_MOTION`, which floods the connection on X11, so it
+/// is left out of the default mask. Events that concern the window's
+/// lifetime, like `EventType::Destroyed`, are always delivered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventMask(u16);
+
+impl EventMask {
+    /// No optional events.
+    pub const NONE: EventMask = EventMask(0);
+    /// Key presses and releases.
+    pub const KEY: EventMask = EventMask(1 << 0);
+    /// Mouse button presses and releases, including the clicks and long
+    /// presses synthesized from them.
+    pub const BUTTON: EventMask = EventMask(1 << 1);
+    /// Pointer motion.
+    ///
+    /// Hover detection learns where the pointer is from motion, so a
+    /// window that leaves this out never receives `MouseHover` events,
+    /// even with hovers enabled by `Runtime::window_set_hover_config`.
+    pub const POINTER_MOTION: EventMask = EventMask(1 << 2);
+    /// The pointer entering and leaving the window.
+    pub const CROSSING: EventMask = EventMask(1 << 3);
+    /// Exposures and redraws.
+    pub const EXPOSURE: EventMask = EventMask(1 << 4);
+    /// Changes to the window's size.
+    pub const STRUCTURE: EventMask = EventMask(1 << 5);
+    /// Raw, unaccelerated mouse motion.
+    ///
+    /// Like `POINTER_MOTION`, this is left out of the default mask.
+    pub const RAW_MOTION: EventMask = EventMask(1 << 6);
+    /// Every optional event.
+    pub const ALL: EventMask = EventMask(0b111_1111);
+
+    /// Tell whether all of the categories in `other` are set in this
+    /// mask.
+    pub fn contains(self, other: EventMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Tell whether no categories are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Get the raw bits of this mask.
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Create a mask from raw bits, ignoring unknown bits.
+    pub fn from_bits_truncate(bits: u16) -> Self {
+        EventMask(bits & Self::ALL.0)
+    }
+
+    /// Get the category an event belongs to, or `None` if it is always
+    /// delivered.
+    pub fn of(ty: &EventType) -> Option<EventMask> {
+        match ty {
+            EventType::KeyDown(_) | EventType::KeyUp(_) | EventType::TextInput(_) => {
+                Some(Self::KEY)
+            }
+            EventType::MouseButtonDown { .. }
+            | EventType::MouseButtonUp { .. }
+            | EventType::MouseDoubleClick { .. }
+            | EventType::MouseTripleClick { .. }
+            | EventType::LongPress { .. }
+            | EventType::MouseWheel { .. } => Some(Self::BUTTON),
+            EventType::MouseMoved { .. } => Some(Self::POINTER_MOTION),
+            EventType::RawMouseMotion { .. } => Some(Self::RAW_MOTION),
+            EventType::MouseEntered { .. } | EventType::MouseLeft => Some(Self::CROSSING),
+            EventType::Expose(_) | EventType::RedrawRequested => Some(Self::EXPOSURE),
+            EventType::Resized { .. } => Some(Self::STRUCTURE),
+            _ => None,
+        }
+    }
+
+    /// Tell whether a window with this mask receives an event.
+    pub fn allows(self, ty: &EventType) -> bool {
+        match Self::of(ty) {
+            Some(category) => self.contains(category),
+            None => true,
+        }
+    }
+}
+
+impl Default for EventMask {
+    /// Every optional event except for pointer motion and raw motion.
+    fn default() -> Self {
+        EventMask(Self::ALL.0 & !Self::POINTER_MOTION.0 & !Self::RAW_MOTION.0)
+    }
+}
+
+impl BitOr for EventMask {
+    type Output = EventMask;
+
+    fn bitor(self, rhs: EventMask) -> EventMask {
+        EventMask(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for EventMask {
+    fn bitor_assign(&mut self, rhs: EventMask) {
+        self.0 |= rhs.0;
+    }
+}
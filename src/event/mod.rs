@@ -0,0 +1,410 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Events produced by the runtime.
+
+mod click;
+mod crossing;
+mod delivery;
+mod hover;
+mod mask;
+mod queue;
+pub mod recorder;
+
+pub use click::{ClickTracker, DEFAULT_DOUBLE_CLICK_INTERVAL};
+pub use crossing::CrossingTracker;
+pub use delivery::EventDelivery;
+pub use hover::{HoverConfig, HoverTracker};
+pub use mask::EventMask;
+pub use queue::{EventQueue, QueuePolicy};
+
+use crate::{
+    accessibility::{AccessAction, AccessNodeId},
+    ControlCommand, KeyInfo, MenuItemId, NativeEvent, RawEvent, Rectangle, ShortcutId, Theme,
+    Window,
+};
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+/// An event produced by the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// The window that this event applies to, if any.
+    window: Option<Window>,
+    /// The type of this event.
+    ty: EventType,
+    /// When the event occurred, on the runtime's monotonic clock.
+    timestamp: Option<Duration>,
+    /// When the event occurred, according to the windowing system.
+    native_time: Option<u32>,
+}
+
+/// The types of events that can be produced by the runtime.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum EventType {
+    /// An assistive technology asked for an action to be performed on a
+    /// node of the window's accessibility tree.
+    AccessAction(AccessNodeId, AccessAction),
+    /// A compositor started or stopped running.
+    ///
+    /// The new state is also reflected in `Capabilities::compositing`.
+    CompositorChanged(bool),
+    /// A native control reported something the user did with it.
+    ///
+    /// See `Runtime::create_control`.
+    ControlCommand(ControlCommand),
+    /// The window was destroyed, and no longer belongs to the runtime.
+    Destroyed,
+    /// The connection to the display server was lost.
+    ///
+    /// What happens next depends on the runtime's `ReconnectPolicy`.
+    DisplayDisconnected,
+    /// The runtime reconnected to the display server and recreated its
+    /// windows.
+    ///
+    /// Anything the windows displayed has to be painted again, and
+    /// resources like images uploaded to the server have to be
+    /// recreated.
+    DisplayReconnected,
+    /// The event queue overflowed, and this many events were dropped.
+    ///
+    /// See `QueuePolicy::capacity`.
+    EventsDropped(u64),
+    /// The system is about to be suspended.
+    ///
+    /// Applications should pause background work until
+    /// `EventType::PowerResume` is delivered.
+    PowerSuspend,
+    /// The system resumed from being suspended.
+    PowerResume,
+    /// Screens were added, removed, resized or rearranged.
+    ///
+    /// Applications should call `Runtime::screens` again. On X11, this
+    /// is driven by RandR's `ScreenChangeNotify`.
+    ScreenConfigurationChanged,
+    /// The window's scale factor changed, e.g. because it was moved to a
+    /// monitor with a different DPI.
+    ///
+    /// Windows should lay themselves out again at the new scale factor.
+    /// On Windows, this comes from `WM_DPICHANGED`, and the window is
+    /// moved to `suggested` unless the application configures it
+    /// otherwise while handling this event.
+    ScaleFactorChanged {
+        /// The new scale factor; see `Runtime::window_scale_factor`.
+        scale_factor: f64,
+        /// The geometry the platform suggests for the window at the new
+        /// scale factor, relative to its parent.
+        suggested: Rectangle,
+    },
+    /// The session is ending, e.g. because the user is logging out.
+    ///
+    /// The application should save its state, and then call
+    /// `Runtime::session_end_response`. If `can_veto` is true, it may
+    /// ask for the session to continue instead.
+    SessionEnding {
+        /// Whether or not the application can prevent the session from
+        /// ending.
+        can_veto: bool,
+    },
+    /// A registered keyboard shortcut was pressed.
+    ///
+    /// See `Runtime::register_shortcut`.
+    Shortcut(ShortcutId),
+    /// The user's preferred color scheme changed.
+    ///
+    /// This is watched through the settings portal on Linux and
+    /// `WM_SETTINGCHANGE` on Windows.
+    SystemThemeChanged(Theme),
+    /// The window should be redrawn.
+    ///
+    /// This is delivered after `Runtime::window_request_redraw`, timed to
+    /// the monitor's refresh so that animations neither tear nor draw
+    /// frames that will never be displayed.
+    RedrawRequested,
+    /// The window was resized.
+    Resized {
+        /// The new width of the window.
+        width: u32,
+        /// The new height of the window.
+        height: u32,
+    },
+    /// A later instance of the application sent its arguments to this
+    /// one.
+    ///
+    /// See `async_runtime::single_instance`.
+    InstanceMessage(Vec<String>),
+    /// Part of a window needs to be repainted.
+    Expose(Rectangle),
+    /// The keyboard mapping changed, e.g. because the user switched to
+    /// another layout.
+    ///
+    /// Keys pressed from now on are translated with the new mapping.
+    /// Applications that display key names, such as in shortcut hints,
+    /// should refresh them.
+    KeymapChanged,
+    /// An item in a menu bar or context menu was activated.
+    MenuItemActivated(MenuItemId),
+    /// The windowing system sent an event that this crate does not
+    /// interpret, but that the application asked to receive.
+    ///
+    /// See `Runtime::register_client_message`.
+    Native(NativeEvent),
+    /// A raw event that a native event filter took away from the
+    /// runtime, to be handled by the application.
+    ///
+    /// See `Runtime::add_native_event_filter`. These events cannot be
+    /// recorded.
+    PlatformSpecific(RawEvent),
+    /// The pointer moved over this window or one of its descendants,
+    /// having been outside of it.
+    ///
+    /// This is delivered to the window and each of its ancestors that the
+    /// pointer was not already over, outermost first.
+    MouseEntered {
+        /// The innermost window now under the pointer, which may be this
+        /// window itself.
+        child: Window,
+    },
+    /// The pointer is no longer over this window or any of its
+    /// descendants.
+    MouseLeft,
+    /// The pointer moved to the given coordinates.
+    MouseMoved {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+    },
+    /// A mouse button was pressed at the given coordinates.
+    MouseButtonDown {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// The button that was pressed.
+        button: MouseButton,
+    },
+    /// A mouse button was released at the given coordinates.
+    MouseButtonUp {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// The button that was released.
+        button: MouseButton,
+    },
+    /// The pointer rested in the same place for the window's hover
+    /// delay, e.g. long enough to show a tooltip.
+    ///
+    /// See `Runtime::window_set_hover_config`.
+    MouseHover {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// How long the pointer has rested there.
+        duration: Duration,
+    },
+    /// A mouse button or finger was held down in the same place for the
+    /// window's long-press delay, e.g. long enough to open a context
+    /// menu on a touch screen.
+    ///
+    /// See `Runtime::window_set_hover_config`.
+    LongPress {
+        /// The X coordinate of the press, relative to the window.
+        x: i32,
+        /// The Y coordinate of the press, relative to the window.
+        y: i32,
+        /// The button that is held down.
+        button: MouseButton,
+    },
+    /// A mouse button was pressed a second time, at about the same
+    /// place and within the double-click interval.
+    ///
+    /// This is delivered after the second `MouseButtonDown`.
+    MouseDoubleClick {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// The button that was pressed.
+        button: MouseButton,
+    },
+    /// A mouse button was pressed a third time, at about the same place
+    /// and within the double-click interval.
+    ///
+    /// This is delivered after the third `MouseButtonDown`.
+    MouseTripleClick {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// The button that was pressed.
+        button: MouseButton,
+    },
+    /// The mouse wheel was scrolled with the pointer at the given
+    /// coordinates.
+    ///
+    /// The deltas are in notches: one click of a common wheel is `1.0`,
+    /// while touchpads and high-resolution wheels produce fractions.
+    /// Positive values scroll up and to the right. On X11, this replaces
+    /// the presses of buttons 4 through 7, which are never delivered as
+    /// `MouseButtonDown`.
+    MouseWheel {
+        /// The X coordinate of the pointer, relative to the window.
+        x: i32,
+        /// The Y coordinate of the pointer, relative to the window.
+        y: i32,
+        /// How far to scroll horizontally.
+        delta_x: f32,
+        /// How far to scroll vertically.
+        delta_y: f32,
+    },
+    /// The mouse moved by the given amount, before pointer acceleration
+    /// was applied and regardless of whether the pointer could move
+    /// further.
+    ///
+    /// This is meant for games and 3D views that capture the pointer.
+    /// It is delivered to the focused window, and only if its event mask
+    /// contains `EventMask::RAW_MOTION`. It comes from `WM_INPUT` on
+    /// Windows and from XInput2 raw events on X11.
+    RawMouseMotion {
+        /// The horizontal distance, in device units.
+        dx: i32,
+        /// The vertical distance, in device units.
+        dy: i32,
+    },
+    /// A key was pressed.
+    KeyDown(KeyInfo),
+    /// A key was released.
+    KeyUp(KeyInfo),
+    /// Text was typed, after the key presses that produced it were
+    /// translated by the keyboard layout and any dead keys or input
+    /// method.
+    ///
+    /// This is delivered after the `KeyDown` that completed the text.
+    /// Text editors should insert this rather than interpreting
+    /// `KeyDown` themselves. It comes from `WM_CHAR` on Windows and from
+    /// `XLookupString` on X11.
+    TextInput(String),
+}
+
+/// A button on the mouse.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MouseButton {
+    /// The left mouse button.
+    Left,
+    /// The middle mouse button, or the scroll wheel.
+    Middle,
+    /// The right mouse button.
+    Right,
+    /// Another mouse button, by index.
+    Other(u8),
+}
+
+impl Event {
+    /// Create a new `Event`, with no timestamps.
+    pub fn new(window: Option<Window>, ty: EventType) -> Self {
+        Event {
+            window,
+            ty,
+            timestamp: None,
+            native_time: None,
+        }
+    }
+
+    /// Note when this event occurred, on the runtime's monotonic clock.
+    ///
+    /// Backends stamp every event they produce, using `monotonic_now`
+    /// where the standard library is available.
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Note when this event occurred according to the windowing system,
+    /// e.g. the `time` field of an X11 event or `GetMessageTime` on
+    /// Windows, in milliseconds.
+    pub fn with_native_time(mut self, native_time: u32) -> Self {
+        self.native_time = Some(native_time);
+        self
+    }
+
+    /// Get the window that this event applies to, if any.
+    pub fn window(&self) -> Option<Window> {
+        self.window
+    }
+
+    /// Get the type of this event.
+    pub fn ty(&self) -> &EventType {
+        &self.ty
+    }
+
+    /// Get the time at which this event occurred, on the runtime's
+    /// monotonic clock, if it is known.
+    pub fn timestamp(&self) -> Option<Duration> {
+        self.timestamp
+    }
+
+    /// Get the time at which this event occurred according to the
+    /// windowing system, in milliseconds, if it is known.
+    ///
+    /// This clock wraps around, and its epoch differs between platforms,
+    /// so it is only meaningful when compared to other native times.
+    pub fn native_time(&self) -> Option<u32> {
+        self.native_time
+    }
+
+    /// Get how long ago this event occurred, given the current time on
+    /// the runtime's monotonic clock.
+    pub fn latency(&self, now: Duration) -> Option<Duration> {
+        now.checked_sub(self.timestamp?)
+    }
+
+    /// Get the time between an earlier event and this one.
+    ///
+    /// This is what double-click detection and gesture velocities are
+    /// measured with.
+    pub fn since(&self, earlier: &Event) -> Option<Duration> {
+        self.timestamp?.checked_sub(earlier.timestamp?)
+    }
+
+    cfg_std! {
+        /// Get how long ago this event occurred, on the clock used by
+        /// `monotonic_now`.
+        pub fn age(&self) -> Option<Duration> {
+            self.latency(monotonic_now())
+        }
+    }
+
+    /// Convert this event into its type.
+    pub fn into_ty(self) -> EventType {
+        self.ty
+    }
+}
+
+cfg_std! {
+    /// Get the current time on the monotonic clock used to timestamp
+    /// events, measured from the first time this is called.
+    pub fn monotonic_now() -> Duration {
+        static EPOCH: once_cell::sync::OnceCell<std::time::Instant> =
+            once_cell::sync::OnceCell::new();
+
+        EPOCH.get_or_init(std::time::Instant::now).elapsed()
+    }
+}
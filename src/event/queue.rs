@@ -0,0 +1,270 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{Event, EventType};
+use alloc::collections::VecDeque;
+use core::num::NonZeroUsize;
+
+/// Policy describing how a runtime's event queue handles floods of
+/// events, such as those produced while dragging.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct QueuePolicy {
+    /// Keep only the latest `MouseMoved`, `RedrawRequested` and
+    /// `Resized` event for each window, replacing earlier ones that have
+    /// not been received yet.
+    pub coalesce: bool,
+    /// The maximum number of events to keep in the queue, or `None` if
+    /// the queue is unbounded.
+    ///
+    /// When the queue is full, the oldest event is dropped, and an
+    /// `EventType::EventsDropped` event is delivered before the next
+    /// event to report how many were lost.
+    pub capacity: Option<NonZeroUsize>,
+}
+
+impl QueuePolicy {
+    /// Create a new `QueuePolicy` that neither coalesces nor bounds
+    /// the queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable coalescing.
+    pub fn with_coalesce(mut self, coalesce: bool) -> Self {
+        self.coalesce = coalesce;
+        self
+    }
+
+    /// Set the maximum number of events to keep in the queue.
+    pub fn with_capacity(mut self, capacity: Option<NonZeroUsize>) -> Self {
+        self.capacity = capacity;
+        self
+    }
+}
+
+/// A queue of events that applies a `QueuePolicy`.
+///
+/// This is intended for use by backends.
+#[derive(Debug, Clone, Default)]
+pub struct EventQueue {
+    policy: QueuePolicy,
+    events: VecDeque<Event>,
+    /// The number of events dropped since the last notification.
+    dropped: u64,
+}
+
+impl EventQueue {
+    /// Create a new, empty `EventQueue` with the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current policy.
+    pub fn policy(&self) -> QueuePolicy {
+        self.policy
+    }
+
+    /// Set the policy.
+    ///
+    /// If the queue holds more events than the new capacity allows,
+    /// the oldest events are dropped.
+    pub fn set_policy(&mut self, policy: QueuePolicy) {
+        self.policy = policy;
+        self.trim();
+    }
+
+    /// Add an event to the back of the queue.
+    pub fn push(&mut self, event: Event) {
+        if self.policy.coalesce && coalesces(event.ty()) {
+            // find the latest event for the same window; if it is the
+            // same kind of event, the new one supersedes it
+            let window = event.window();
+            let latest = self
+                .events
+                .iter_mut()
+                .rev()
+                .find(|queued| queued.window() == window);
+
+            if let Some(latest) = latest {
+                if same_kind(latest.ty(), event.ty()) {
+                    *latest = event;
+                    return;
+                }
+            }
+        }
+
+        self.events.push_back(event);
+        self.trim();
+    }
+
+    /// Take the event at the front of the queue.
+    ///
+    /// If events were dropped since the last call, an
+    /// `EventType::EventsDropped` event is returned first.
+    pub fn pop(&mut self) -> Option<Event> {
+        if self.dropped != 0 {
+            let dropped = core::mem::take(&mut self.dropped);
+            return Some(Event::new(None, EventType::EventsDropped(dropped)));
+        }
+
+        self.events.pop_front()
+    }
+
+    /// Get the number of events waiting in the queue.
+    pub fn len(&self) -> usize {
+        self.events.len() + (self.dropped != 0) as usize
+    }
+
+    /// Tell whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every event from the queue.
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.dropped = 0;
+    }
+
+    fn trim(&mut self) {
+        if let Some(capacity) = self.policy.capacity {
+            while self.events.len() > capacity.get() {
+                self.events.pop_front();
+                self.dropped += 1;
+            }
+        }
+    }
+}
+
+impl Extend<Event> for EventQueue {
+    fn extend<I: IntoIterator<Item = Event>>(&mut self, iter: I) {
+        for event in iter {
+            self.push(event);
+        }
+    }
+}
+
+/// Tell whether an event of this type may be replaced by a later one.
+fn coalesces(ty: &EventType) -> bool {
+    matches!(
+        ty,
+        EventType::MouseMoved { .. } | EventType::RedrawRequested | EventType::Resized { .. }
+    )
+}
+
+fn same_kind(a: &EventType, b: &EventType) -> bool {
+    core::mem::discriminant(a) == core::mem::discriminant(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Window;
+    use alloc::vec::Vec;
+
+    fn window(id: usize) -> Window {
+        Window::new(NonZeroUsize::new(id).unwrap())
+    }
+
+    fn moved(window: Window, x: i32) -> Event {
+        Event::new(Some(window), EventType::MouseMoved { x, y: 0 })
+    }
+
+    fn drain(queue: &mut EventQueue) -> Vec<EventType> {
+        core::iter::from_fn(|| queue.pop())
+            .map(|event| event.ty().clone())
+            .collect()
+    }
+
+    #[test]
+    fn coalesces_latest_event_per_window() {
+        let mut queue = EventQueue::new();
+        queue.set_policy(QueuePolicy::new().with_coalesce(true));
+        let (a, b) = (window(1), window(2));
+        queue.extend([moved(a, 1), moved(b, 1), moved(a, 2), moved(b, 2)]);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            drain(&mut queue),
+            [
+                EventType::MouseMoved { x: 2, y: 0 },
+                EventType::MouseMoved { x: 2, y: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_coalesce_across_other_events() {
+        let mut queue = EventQueue::new();
+        queue.set_policy(QueuePolicy::new().with_coalesce(true));
+        let a = window(1);
+        queue.extend([
+            moved(a, 1),
+            Event::new(Some(a), EventType::RedrawRequested),
+            moved(a, 2),
+            Event::new(Some(a), EventType::RedrawRequested),
+        ]);
+
+        // only the latest event for the window can be replaced, and it
+        // is always of the other kind
+        assert_eq!(queue.len(), 4);
+    }
+
+    #[test]
+    fn keeps_everything_without_coalescing() {
+        let mut queue = EventQueue::new();
+        let a = window(1);
+        queue.extend([moved(a, 1), moved(a, 2)]);
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn drops_oldest_events_past_capacity() {
+        let mut queue = EventQueue::new();
+        queue.set_policy(QueuePolicy::new().with_capacity(NonZeroUsize::new(2)));
+        let a = window(1);
+        queue.extend((0..5).map(|x| moved(a, x)));
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(
+            drain(&mut queue),
+            [
+                EventType::EventsDropped(3),
+                EventType::MouseMoved { x: 3, y: 0 },
+                EventType::MouseMoved { x: 4, y: 0 },
+            ]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn shrinking_capacity_drops_events() {
+        let mut queue = EventQueue::new();
+        let a = window(1);
+        queue.extend((0..3).map(|x| moved(a, x)));
+        queue.set_policy(QueuePolicy::new().with_capacity(NonZeroUsize::new(1)));
+
+        assert_eq!(
+            drain(&mut queue),
+            [
+                EventType::EventsDropped(2),
+                EventType::MouseMoved { x: 2, y: 0 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,702 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Recording and replaying event streams.
+//!
+//! A `Recorder` serializes events, along with the time at which they
+//! occurred, into a compact binary format. A `Replay` parses that
+//! format and injects the events back into a runtime in the same
+//! order, which is useful for reproducing bugs and driving UI tests.
+//!
+//! Window identifiers are recorded as-is, so the windows must be
+//! created in the same order before replaying.
+
+use super::{Event, EventType, MouseButton};
+use crate::{
+    accessibility::{AccessAction, AccessNodeId},
+    async_runtime::Runtime,
+    ClientMessage, ClientMessageData, ControlCommand, Error, Key, KeyInfo, MenuItemId, Modifiers,
+    NativeEvent, Rectangle, Result, ShortcutId, Theme, Window,
+};
+use alloc::vec::Vec;
+use core::{convert::TryInto, num::NonZeroUsize, time::Duration};
+
+const MAGIC: &[u8; 4] = b"GTEV";
+const VERSION: u8 = 2;
+
+// event type tags
+const TAG_EXPOSE: u8 = 0;
+const TAG_MENU_ITEM_ACTIVATED: u8 = 1;
+const TAG_MOUSE_BUTTON_DOWN: u8 = 2;
+const TAG_MOUSE_BUTTON_UP: u8 = 3;
+const TAG_KEY_DOWN: u8 = 4;
+const TAG_KEY_UP: u8 = 5;
+const TAG_RESIZED: u8 = 6;
+const TAG_COMPOSITOR_CHANGED: u8 = 7;
+const TAG_DESTROYED: u8 = 8;
+const TAG_MOUSE_MOVED: u8 = 9;
+const TAG_EVENTS_DROPPED: u8 = 10;
+const TAG_INSTANCE_MESSAGE: u8 = 11;
+const TAG_SESSION_ENDING: u8 = 12;
+const TAG_SYSTEM_THEME_CHANGED: u8 = 13;
+const TAG_POWER_SUSPEND: u8 = 14;
+const TAG_POWER_RESUME: u8 = 15;
+const TAG_ACCESS_ACTION: u8 = 16;
+const TAG_SHORTCUT: u8 = 17;
+const TAG_REDRAW_REQUESTED: u8 = 18;
+const TAG_SCREEN_CONFIGURATION_CHANGED: u8 = 19;
+const TAG_MOUSE_DOUBLE_CLICK: u8 = 20;
+const TAG_MOUSE_TRIPLE_CLICK: u8 = 21;
+const TAG_MOUSE_HOVER: u8 = 22;
+const TAG_LONG_PRESS: u8 = 23;
+const TAG_MOUSE_ENTERED: u8 = 24;
+const TAG_MOUSE_LEFT: u8 = 25;
+const TAG_DISPLAY_DISCONNECTED: u8 = 26;
+const TAG_DISPLAY_RECONNECTED: u8 = 27;
+const TAG_KEYMAP_CHANGED: u8 = 28;
+const TAG_CONTROL_COMMAND: u8 = 29;
+const TAG_MOUSE_WHEEL: u8 = 30;
+const TAG_RAW_MOUSE_MOTION: u8 = 31;
+const TAG_TEXT_INPUT: u8 = 32;
+const TAG_SCALE_FACTOR_CHANGED: u8 = 33;
+const TAG_NATIVE: u8 = 34;
+
+/// Records a stream of events into a compact binary format.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    buffer: Vec<u8>,
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(MAGIC);
+        buffer.push(VERSION);
+
+        Recorder {
+            buffer,
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+impl Recorder {
+    /// Create a new, empty `Recorder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an event that occurred at `time`, measured from the
+    /// start of the recording.
+    ///
+    /// Returns an error if the event's type cannot be recorded.
+    pub fn record_at(&mut self, time: Duration, event: &Event) -> Result {
+        let start = self.buffer.len();
+        let micros = time.as_micros().min(u64::MAX as u128) as u64;
+        self.buffer.extend_from_slice(&micros.to_le_bytes());
+        let window = event.window().map_or(0, |w| w.id().get()) as u64;
+        self.buffer.extend_from_slice(&window.to_le_bytes());
+
+        if let Err(err) = encode_type(&mut self.buffer, event.ty()) {
+            // don't leave a partial record behind
+            self.buffer.truncate(start);
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    cfg_std! {
+        /// Record an event that occurred just now.
+        pub fn record(&mut self, event: &Event) -> Result {
+            let time = self.start.elapsed();
+            self.record_at(time, event)
+        }
+    }
+
+    /// Get the recorded data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Convert this recorder into the recorded data.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+/// A parsed recording of events, ready to be replayed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Replay {
+    events: Vec<(Duration, Event)>,
+}
+
+impl Replay {
+    /// Parse a recording produced by a `Recorder`.
+    pub fn parse(mut data: &[u8]) -> Result<Self> {
+        let header = take(&mut data, MAGIC.len() + 1)?;
+        if &header[..MAGIC.len()] != MAGIC || header[MAGIC.len()] != VERSION {
+            return Err(Error::static_msg("Not a recognized event recording"));
+        }
+
+        let mut events = Vec::new();
+        while !data.is_empty() {
+            let time = Duration::from_micros(take_u64(&mut data)?);
+            let window = NonZeroUsize::new(take_u64(&mut data)? as usize).map(Window::new);
+            let ty = decode_type(&mut data)?;
+            events.push((time, Event::new(window, ty)));
+        }
+
+        Ok(Replay { events })
+    }
+
+    /// Get the recorded events, along with the times at which they
+    /// occurred.
+    pub fn events(&self) -> &[(Duration, Event)] {
+        &self.events
+    }
+
+    /// Inject every recorded event into the runtime, in order.
+    ///
+    /// Events are injected back-to-back, without waiting between them,
+    /// so that replays are deterministic. Each event is timestamped with
+    /// the time it was recorded at, so that timing-dependent input, like
+    /// double clicks, is recognized the same way.
+    ///
+    /// Events that the runtime synthesizes from other input, like double
+    /// clicks and hovers, are skipped, since the runtime produces them
+    /// again from the replayed input.
+    pub async fn run<R: Runtime + ?Sized>(self, runtime: &mut R) -> Result {
+        for (time, event) in self.events {
+            if !synthesized(event.ty()) {
+                runtime.inject_event(event.with_timestamp(time)).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tell whether the runtime produces events of this type from other
+/// input, rather than receiving them from the windowing system.
+fn synthesized(ty: &EventType) -> bool {
+    matches!(
+        ty,
+        EventType::MouseDoubleClick { .. }
+            | EventType::MouseTripleClick { .. }
+            | EventType::MouseHover { .. }
+            | EventType::LongPress { .. }
+    )
+}
+
+fn encode_type(buf: &mut Vec<u8>, ty: &EventType) -> Result {
+    match ty {
+        EventType::Expose(rect) => {
+            buf.push(TAG_EXPOSE);
+            buf.extend_from_slice(&rect.x.to_le_bytes());
+            buf.extend_from_slice(&rect.y.to_le_bytes());
+            buf.extend_from_slice(&rect.width.to_le_bytes());
+            buf.extend_from_slice(&rect.height.to_le_bytes());
+        }
+        EventType::CompositorChanged(compositing) => {
+            buf.push(TAG_COMPOSITOR_CHANGED);
+            buf.push(*compositing as u8);
+        }
+        EventType::Destroyed => buf.push(TAG_DESTROYED),
+        EventType::DisplayDisconnected => buf.push(TAG_DISPLAY_DISCONNECTED),
+        EventType::DisplayReconnected => buf.push(TAG_DISPLAY_RECONNECTED),
+        EventType::KeymapChanged => buf.push(TAG_KEYMAP_CHANGED),
+        EventType::EventsDropped(count) => {
+            buf.push(TAG_EVENTS_DROPPED);
+            buf.extend_from_slice(&count.to_le_bytes());
+        }
+        EventType::InstanceMessage(args) => {
+            buf.push(TAG_INSTANCE_MESSAGE);
+            buf.extend_from_slice(&(args.len() as u32).to_le_bytes());
+            for arg in args {
+                buf.extend_from_slice(&(arg.len() as u32).to_le_bytes());
+                buf.extend_from_slice(arg.as_bytes());
+            }
+        }
+        EventType::MouseMoved { x, y } => {
+            buf.push(TAG_MOUSE_MOVED);
+            buf.extend_from_slice(&x.to_le_bytes());
+            buf.extend_from_slice(&y.to_le_bytes());
+        }
+        EventType::MouseWheel {
+            x,
+            y,
+            delta_x,
+            delta_y,
+        } => {
+            buf.push(TAG_MOUSE_WHEEL);
+            buf.extend_from_slice(&x.to_le_bytes());
+            buf.extend_from_slice(&y.to_le_bytes());
+            buf.extend_from_slice(&delta_x.to_bits().to_le_bytes());
+            buf.extend_from_slice(&delta_y.to_bits().to_le_bytes());
+        }
+        EventType::RawMouseMotion { dx, dy } => {
+            buf.push(TAG_RAW_MOUSE_MOTION);
+            buf.extend_from_slice(&dx.to_le_bytes());
+            buf.extend_from_slice(&dy.to_le_bytes());
+        }
+        EventType::TextInput(text) => {
+            buf.push(TAG_TEXT_INPUT);
+            buf.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            buf.extend_from_slice(text.as_bytes());
+        }
+        EventType::MouseEntered { child } => {
+            buf.push(TAG_MOUSE_ENTERED);
+            buf.extend_from_slice(&(child.id().get() as u64).to_le_bytes());
+        }
+        EventType::MouseLeft => buf.push(TAG_MOUSE_LEFT),
+        EventType::MouseHover { x, y, duration } => {
+            buf.push(TAG_MOUSE_HOVER);
+            buf.extend_from_slice(&x.to_le_bytes());
+            buf.extend_from_slice(&y.to_le_bytes());
+            let micros = duration.as_micros().min(u64::MAX as u128) as u64;
+            buf.extend_from_slice(&micros.to_le_bytes());
+        }
+        EventType::AccessAction(node, action) => {
+            buf.push(TAG_ACCESS_ACTION);
+            buf.extend_from_slice(&node.id().to_le_bytes());
+            buf.push(match action {
+                AccessAction::Activate => 0,
+                AccessAction::Focus => 1,
+                AccessAction::ScrollIntoView => 2,
+            });
+        }
+        EventType::PowerSuspend => buf.push(TAG_POWER_SUSPEND),
+        EventType::PowerResume => buf.push(TAG_POWER_RESUME),
+        EventType::Shortcut(id) => {
+            buf.push(TAG_SHORTCUT);
+            buf.extend_from_slice(&id.id().to_le_bytes());
+        }
+        EventType::SystemThemeChanged(theme) => {
+            buf.push(TAG_SYSTEM_THEME_CHANGED);
+            buf.push(match theme {
+                Theme::Light => 0,
+                Theme::Dark => 1,
+            });
+        }
+        EventType::ScreenConfigurationChanged => buf.push(TAG_SCREEN_CONFIGURATION_CHANGED),
+        EventType::ScaleFactorChanged {
+            scale_factor,
+            suggested,
+        } => {
+            buf.push(TAG_SCALE_FACTOR_CHANGED);
+            buf.extend_from_slice(&scale_factor.to_bits().to_le_bytes());
+            buf.extend_from_slice(&suggested.x.to_le_bytes());
+            buf.extend_from_slice(&suggested.y.to_le_bytes());
+            buf.extend_from_slice(&suggested.width.to_le_bytes());
+            buf.extend_from_slice(&suggested.height.to_le_bytes());
+        }
+        EventType::SessionEnding { can_veto } => {
+            buf.push(TAG_SESSION_ENDING);
+            buf.push(*can_veto as u8);
+        }
+        EventType::RedrawRequested => buf.push(TAG_REDRAW_REQUESTED),
+        EventType::Resized { width, height } => {
+            buf.push(TAG_RESIZED);
+            buf.extend_from_slice(&width.to_le_bytes());
+            buf.extend_from_slice(&height.to_le_bytes());
+        }
+        EventType::ControlCommand(command) => {
+            buf.push(TAG_CONTROL_COMMAND);
+            buf.push(match command {
+                ControlCommand::Clicked => 0,
+                ControlCommand::Changed => 1,
+            });
+        }
+        EventType::Native(NativeEvent::ClientMessage(message)) => {
+            buf.push(TAG_NATIVE);
+            buf.extend_from_slice(&message.message_type.to_le_bytes());
+            match message.data {
+                ClientMessageData::Format8(data) => {
+                    buf.push(8);
+                    buf.extend_from_slice(&data);
+                }
+                ClientMessageData::Format16(data) => {
+                    buf.push(16);
+                    data.iter()
+                        .for_each(|value| buf.extend_from_slice(&value.to_le_bytes()));
+                }
+                ClientMessageData::Format32(data) => {
+                    buf.push(32);
+                    data.iter()
+                        .for_each(|value| buf.extend_from_slice(&value.to_le_bytes()));
+                }
+            }
+        }
+        EventType::MenuItemActivated(id) => {
+            buf.push(TAG_MENU_ITEM_ACTIVATED);
+            buf.extend_from_slice(&id.id().to_le_bytes());
+        }
+        EventType::MouseButtonDown { x, y, button }
+        | EventType::MouseButtonUp { x, y, button }
+        | EventType::MouseDoubleClick { x, y, button }
+        | EventType::MouseTripleClick { x, y, button }
+        | EventType::LongPress { x, y, button } => {
+            buf.push(match ty {
+                EventType::MouseButtonDown { .. } => TAG_MOUSE_BUTTON_DOWN,
+                EventType::MouseButtonUp { .. } => TAG_MOUSE_BUTTON_UP,
+                EventType::MouseDoubleClick { .. } => TAG_MOUSE_DOUBLE_CLICK,
+                EventType::MouseTripleClick { .. } => TAG_MOUSE_TRIPLE_CLICK,
+                _ => TAG_LONG_PRESS,
+            });
+            buf.extend_from_slice(&x.to_le_bytes());
+            buf.extend_from_slice(&y.to_le_bytes());
+            let (tag, value) = match *button {
+                MouseButton::Left => (0, 0),
+                MouseButton::Middle => (1, 0),
+                MouseButton::Right => (2, 0),
+                MouseButton::Other(n) => (3, n),
+            };
+            buf.push(tag);
+            buf.push(value);
+        }
+        EventType::KeyDown(info) | EventType::KeyUp(info) => {
+            buf.push(match ty {
+                EventType::KeyDown(_) => TAG_KEY_DOWN,
+                _ => TAG_KEY_UP,
+            });
+            buf.push(info.modifiers.bits());
+            encode_key(buf, info.key);
+        }
+        #[allow(unreachable_patterns)]
+        _ => return Err(Error::unsupported("recording this event type")),
+    }
+
+    Ok(())
+}
+
+fn decode_type(data: &mut &[u8]) -> Result<EventType> {
+    let ty = match take_u8(data)? {
+        TAG_EXPOSE => EventType::Expose(Rectangle::new(
+            take_i32(data)?,
+            take_i32(data)?,
+            take_u32(data)?,
+            take_u32(data)?,
+        )),
+        TAG_COMPOSITOR_CHANGED => EventType::CompositorChanged(take_u8(data)? != 0),
+        TAG_DESTROYED => EventType::Destroyed,
+        TAG_DISPLAY_DISCONNECTED => EventType::DisplayDisconnected,
+        TAG_DISPLAY_RECONNECTED => EventType::DisplayReconnected,
+        TAG_KEYMAP_CHANGED => EventType::KeymapChanged,
+        TAG_EVENTS_DROPPED => EventType::EventsDropped(take_u64(data)?),
+        TAG_INSTANCE_MESSAGE => {
+            let count = take_u32(data)?;
+            let mut args = Vec::new();
+            for _ in 0..count {
+                let len = take_u32(data)? as usize;
+                let arg = core::str::from_utf8(take(data, len)?)
+                    .map_err(|_| Error::static_msg("Invalid string in recording"))?;
+                args.push(arg.into());
+            }
+            EventType::InstanceMessage(args)
+        }
+        TAG_MOUSE_MOVED => EventType::MouseMoved {
+            x: take_i32(data)?,
+            y: take_i32(data)?,
+        },
+        TAG_MOUSE_WHEEL => EventType::MouseWheel {
+            x: take_i32(data)?,
+            y: take_i32(data)?,
+            delta_x: f32::from_bits(take_u32(data)?),
+            delta_y: f32::from_bits(take_u32(data)?),
+        },
+        TAG_RAW_MOUSE_MOTION => EventType::RawMouseMotion {
+            dx: take_i32(data)?,
+            dy: take_i32(data)?,
+        },
+        TAG_TEXT_INPUT => {
+            let len = take_u32(data)? as usize;
+            let text = core::str::from_utf8(take(data, len)?)
+                .map_err(|_| Error::static_msg("Invalid string in recording"))?;
+            EventType::TextInput(text.into())
+        }
+        TAG_MOUSE_ENTERED => {
+            let child = NonZeroUsize::new(take_u64(data)? as usize)
+                .ok_or_else(|| Error::static_msg("Invalid window in recording"))?;
+            EventType::MouseEntered {
+                child: Window::new(child),
+            }
+        }
+        TAG_MOUSE_LEFT => EventType::MouseLeft,
+        TAG_MOUSE_HOVER => EventType::MouseHover {
+            x: take_i32(data)?,
+            y: take_i32(data)?,
+            duration: Duration::from_micros(take_u64(data)?),
+        },
+        TAG_ACCESS_ACTION => {
+            let node = AccessNodeId::new(take_u32(data)?);
+            let action = match take_u8(data)? {
+                0 => AccessAction::Activate,
+                1 => AccessAction::Focus,
+                2 => AccessAction::ScrollIntoView,
+                _ => {
+                    return Err(Error::static_msg(
+                        "Unknown accessibility action in recording",
+                    ))
+                }
+            };
+            EventType::AccessAction(node, action)
+        }
+        TAG_POWER_SUSPEND => EventType::PowerSuspend,
+        TAG_POWER_RESUME => EventType::PowerResume,
+        TAG_SHORTCUT => EventType::Shortcut(ShortcutId::new(take_u32(data)?)),
+        TAG_SYSTEM_THEME_CHANGED => EventType::SystemThemeChanged(match take_u8(data)? {
+            0 => Theme::Light,
+            1 => Theme::Dark,
+            _ => return Err(Error::static_msg("Unknown theme in recording")),
+        }),
+        TAG_SCREEN_CONFIGURATION_CHANGED => EventType::ScreenConfigurationChanged,
+        TAG_SCALE_FACTOR_CHANGED => EventType::ScaleFactorChanged {
+            scale_factor: f64::from_bits(take_u64(data)?),
+            suggested: Rectangle::new(
+                take_i32(data)?,
+                take_i32(data)?,
+                take_u32(data)?,
+                take_u32(data)?,
+            ),
+        },
+        TAG_SESSION_ENDING => EventType::SessionEnding {
+            can_veto: take_u8(data)? != 0,
+        },
+        TAG_REDRAW_REQUESTED => EventType::RedrawRequested,
+        TAG_RESIZED => EventType::Resized {
+            width: take_u32(data)?,
+            height: take_u32(data)?,
+        },
+        TAG_CONTROL_COMMAND => EventType::ControlCommand(match take_u8(data)? {
+            0 => ControlCommand::Clicked,
+            1 => ControlCommand::Changed,
+            _ => return Err(Error::static_msg("Unknown control command in recording")),
+        }),
+        TAG_NATIVE => {
+            let message_type = take_u32(data)?;
+            let payload = match take_u8(data)? {
+                8 => ClientMessageData::Format8(take(data, 20)?.try_into().unwrap()),
+                16 => {
+                    let mut values = [0; 10];
+                    for value in values.iter_mut() {
+                        *value = u16::from_le_bytes(take(data, 2)?.try_into().unwrap());
+                    }
+                    ClientMessageData::Format16(values)
+                }
+                32 => {
+                    let mut values = [0; 5];
+                    for value in values.iter_mut() {
+                        *value = take_u32(data)?;
+                    }
+                    ClientMessageData::Format32(values)
+                }
+                _ => return Err(Error::static_msg("Unknown message format in recording")),
+            };
+            EventType::Native(NativeEvent::ClientMessage(ClientMessage {
+                message_type,
+                data: payload,
+            }))
+        }
+        TAG_MENU_ITEM_ACTIVATED => EventType::MenuItemActivated(MenuItemId::new(take_u32(data)?)),
+        tag @ TAG_MOUSE_BUTTON_DOWN
+        | tag @ TAG_MOUSE_BUTTON_UP
+        | tag @ TAG_MOUSE_DOUBLE_CLICK
+        | tag @ TAG_MOUSE_TRIPLE_CLICK
+        | tag @ TAG_LONG_PRESS => {
+            let x = take_i32(data)?;
+            let y = take_i32(data)?;
+            let button = match (take_u8(data)?, take_u8(data)?) {
+                (0, _) => MouseButton::Left,
+                (1, _) => MouseButton::Middle,
+                (2, _) => MouseButton::Right,
+                (3, n) => MouseButton::Other(n),
+                _ => return Err(Error::static_msg("Unknown mouse button in recording")),
+            };
+
+            match tag {
+                TAG_MOUSE_BUTTON_DOWN => EventType::MouseButtonDown { x, y, button },
+                TAG_MOUSE_BUTTON_UP => EventType::MouseButtonUp { x, y, button },
+                TAG_MOUSE_DOUBLE_CLICK => EventType::MouseDoubleClick { x, y, button },
+                TAG_MOUSE_TRIPLE_CLICK => EventType::MouseTripleClick { x, y, button },
+                _ => EventType::LongPress { x, y, button },
+            }
+        }
+        tag @ TAG_KEY_DOWN | tag @ TAG_KEY_UP => {
+            let modifiers = Modifiers::from_bits_truncate(take_u8(data)?);
+            let info = KeyInfo::new(decode_key(data)?, modifiers);
+
+            if tag == TAG_KEY_DOWN {
+                EventType::KeyDown(info)
+            } else {
+                EventType::KeyUp(info)
+            }
+        }
+        _ => return Err(Error::static_msg("Unknown event type in recording")),
+    };
+
+    Ok(ty)
+}
+
+fn encode_key(buf: &mut Vec<u8>, key: Key) {
+    let (tag, value) = match key {
+        Key::Char(c) => (0, c as u32),
+        Key::Function(n) => (1, n as u32),
+        Key::Space => (2, 0),
+        Key::Escape => (3, 0),
+        Key::Enter => (4, 0),
+        Key::Tab => (5, 0),
+        Key::Backspace => (6, 0),
+        Key::Delete => (7, 0),
+        Key::Insert => (8, 0),
+        Key::Home => (9, 0),
+        Key::End => (10, 0),
+        Key::PageUp => (11, 0),
+        Key::PageDown => (12, 0),
+        Key::Up => (13, 0),
+        Key::Down => (14, 0),
+        Key::Left => (15, 0),
+        Key::Right => (16, 0),
+    };
+
+    buf.push(tag);
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn decode_key(data: &mut &[u8]) -> Result<Key> {
+    let tag = take_u8(data)?;
+    let value = take_u32(data)?;
+
+    let key = match tag {
+        0 => Key::Char(
+            core::char::from_u32(value)
+                .ok_or_else(|| Error::static_msg("Invalid character in recording"))?,
+        ),
+        1 => Key::Function(value as u8),
+        2 => Key::Space,
+        3 => Key::Escape,
+        4 => Key::Enter,
+        5 => Key::Tab,
+        6 => Key::Backspace,
+        7 => Key::Delete,
+        8 => Key::Insert,
+        9 => Key::Home,
+        10 => Key::End,
+        11 => Key::PageUp,
+        12 => Key::PageDown,
+        13 => Key::Up,
+        14 => Key::Down,
+        15 => Key::Left,
+        16 => Key::Right,
+        _ => return Err(Error::static_msg("Unknown key in recording")),
+    };
+
+    Ok(key)
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if data.len() < len {
+        return Err(Error::static_msg("Event recording is truncated"));
+    }
+
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+fn take_u8(data: &mut &[u8]) -> Result<u8> {
+    Ok(take(data, 1)?[0])
+}
+
+fn take_u32(data: &mut &[u8]) -> Result<u32> {
+    Ok(u32::from_le_bytes(take(data, 4)?.try_into().unwrap()))
+}
+
+fn take_i32(data: &mut &[u8]) -> Result<i32> {
+    Ok(i32::from_le_bytes(take(data, 4)?.try_into().unwrap()))
+}
+
+fn take_u64(data: &mut &[u8]) -> Result<u64> {
+    Ok(u64::from_le_bytes(take(data, 8)?.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{now, TestRuntime},
+        EventMask, HoverConfig, WindowProps,
+    };
+
+    fn hovering_runtime() -> (TestRuntime, Window) {
+        let mut runtime = TestRuntime::new();
+        let mut props = WindowProps::new();
+        props.event_mask = EventMask::ALL;
+        let window = now(runtime.create_window(props)).unwrap();
+        let config = HoverConfig::new().with_hover_delay(Duration::from_millis(500));
+        now(runtime.window_set_hover_config(window, config)).unwrap();
+        (runtime, window)
+    }
+
+    fn drain(runtime: &mut TestRuntime) -> Vec<Event> {
+        let mut events = Vec::new();
+        while runtime.pending_events() > 0 {
+            events.push(now(runtime.next_event()).unwrap());
+        }
+        events
+    }
+
+    #[test]
+    fn replay_matches_recording() {
+        let (mut runtime, window) = hovering_runtime();
+        let button = MouseButton::Left;
+        let input = [
+            (0, EventType::MouseButtonDown { x: 5, y: 5, button }),
+            (10, EventType::MouseButtonUp { x: 5, y: 5, button }),
+            (100, EventType::MouseButtonDown { x: 5, y: 5, button }),
+            (110, EventType::MouseButtonUp { x: 5, y: 5, button }),
+            (200, EventType::MouseMoved { x: 50, y: 50 }),
+            (1000, EventType::MouseMoved { x: 90, y: 90 }),
+        ];
+        for (millis, ty) in input.iter().cloned() {
+            let event = Event::new(Some(window), ty).with_timestamp(Duration::from_millis(millis));
+            now(runtime.inject_event(event)).unwrap();
+        }
+        let recorded = drain(&mut runtime);
+        assert!(recorded
+            .iter()
+            .any(|event| matches!(event.ty(), EventType::MouseDoubleClick { .. })));
+        assert!(recorded
+            .iter()
+            .any(|event| matches!(event.ty(), EventType::MouseHover { .. })));
+
+        let mut recorder = Recorder::new();
+        for event in &recorded {
+            recorder
+                .record_at(event.timestamp().unwrap(), event)
+                .unwrap();
+        }
+
+        let (mut runtime, _) = hovering_runtime();
+        let replay = Replay::parse(recorder.as_bytes()).unwrap();
+        now(replay.run(&mut runtime)).unwrap();
+        assert_eq!(drain(&mut runtime), recorded);
+    }
+}
@@ -0,0 +1,231 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Enumerating and matching the fonts installed on the system.
+//!
+//! On Linux and other Unix-likes, the system fonts are listed by
+//! spawning fontconfig's `fc-list`.
+
+use alloc::{string::String, vec::Vec};
+
+/// The weight, or boldness, of a font, from 1 to 1000.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    /// The thinnest weight.
+    pub const THIN: FontWeight = FontWeight(100);
+    /// A light weight.
+    pub const LIGHT: FontWeight = FontWeight(300);
+    /// The normal weight.
+    pub const NORMAL: FontWeight = FontWeight(400);
+    /// A medium weight.
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    /// A bold weight.
+    pub const BOLD: FontWeight = FontWeight(700);
+    /// The heaviest weight.
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+/// The slant of a font.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontStyle {
+    /// An upright font.
+    #[default]
+    Normal,
+    /// A font designed to be slanted.
+    Italic,
+    /// An upright font that has been slanted.
+    Oblique,
+}
+
+/// Information about an installed font.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FontInfo {
+    /// The family the font belongs to, e.g. "DejaVu Sans".
+    pub family: String,
+    /// The weight of the font.
+    pub weight: FontWeight,
+    /// The slant of the font.
+    pub style: FontStyle,
+    /// The file that the font is loaded from, if known.
+    pub file: Option<String>,
+}
+
+/// A handle to a font in a `Fonts` collection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct FontHandle(usize);
+
+/// A collection of fonts that can be searched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fonts {
+    fonts: Vec<FontInfo>,
+}
+
+impl Fonts {
+    /// Create a collection from a list of fonts.
+    pub fn new(fonts: Vec<FontInfo>) -> Self {
+        Fonts { fonts }
+    }
+
+    cfg_std! {
+        /// List the fonts installed on the system.
+        pub fn system_fonts() -> crate::Result<Self> {
+            imp::system_fonts().map(Fonts::new)
+        }
+    }
+
+    /// Get the information for a font in this collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle came from a different collection.
+    pub fn get(&self, handle: FontHandle) -> &FontInfo {
+        &self.fonts[handle.0]
+    }
+
+    /// Iterate over the fonts in this collection.
+    pub fn iter(&self) -> impl Iterator<Item = (FontHandle, &FontInfo)> + '_ {
+        self.fonts
+            .iter()
+            .enumerate()
+            .map(|(i, font)| (FontHandle(i), font))
+    }
+
+    /// Get the names of the font families in this collection, sorted and
+    /// without duplicates.
+    pub fn families(&self) -> Vec<&str> {
+        let mut families: Vec<&str> = self.fonts.iter().map(|f| f.family.as_str()).collect();
+        families.sort_unstable();
+        families.dedup();
+        families
+    }
+
+    /// Find the font in `family` that best matches the given weight and
+    /// style, or `None` if the family is not in this collection.
+    ///
+    /// Family names are compared without regard to case. Fonts with the
+    /// requested style are preferred, then italic and oblique fonts are
+    /// substituted for one another, and finally the closest weight is
+    /// picked.
+    pub fn find(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<FontHandle> {
+        let style_rank = |other: FontStyle| match (style, other) {
+            (a, b) if a == b => 0,
+            (FontStyle::Italic, FontStyle::Oblique) | (FontStyle::Oblique, FontStyle::Italic) => 1,
+            _ => 2,
+        };
+
+        self.iter()
+            .filter(|(_, font)| font.family.eq_ignore_ascii_case(family))
+            .min_by_key(|(_, font)| {
+                let distance = (font.weight.0 as i32 - weight.0 as i32).abs();
+                // on a tie, lean bolder for bold requests and lighter
+                // otherwise
+                let lean = (font.weight < weight) == (weight >= FontWeight::MEDIUM);
+                (style_rank(font.style), distance, lean)
+            })
+            .map(|(handle, _)| handle)
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+mod imp {
+    use super::{FontInfo, FontStyle, FontWeight};
+    use crate::{Error, Result};
+    use alloc::{string::String, vec::Vec};
+    use std::process::Command;
+
+    pub(super) fn system_fonts() -> Result<Vec<FontInfo>> {
+        tracing::trace!("Spawning fc-list to list fonts");
+        let output = Command::new("fc-list")
+            .arg("--format")
+            .arg("%{family[0]}\\t%{weight}\\t%{slant}\\t%{file}\\n")
+            .output()?;
+
+        if !output.status.success() {
+            tracing::error!("fc-list exited unexpectedly: {:?}", output.status);
+            return Err(Error::unsupported("listing fonts without fontconfig"));
+        }
+
+        let list = String::from_utf8_lossy(&output.stdout);
+        Ok(list.lines().filter_map(parse_line).collect())
+    }
+
+    fn parse_line(line: &str) -> Option<FontInfo> {
+        let mut fields = line.split('\t');
+        let family = fields.next()?;
+        let weight = fontconfig_number(fields.next()?)?;
+        let slant = fontconfig_number(fields.next()?)?;
+        let file = fields.next().filter(|file| !file.is_empty());
+
+        Some(FontInfo {
+            family: family.into(),
+            weight: fontconfig_weight(weight),
+            style: match slant {
+                0 => FontStyle::Normal,
+                100 => FontStyle::Italic,
+                _ => FontStyle::Oblique,
+            },
+            file: file.map(String::from),
+        })
+    }
+
+    /// Parse a number from fontconfig, which is written as a range like
+    /// `[40 200]` for variable fonts; the start of the range is used.
+    fn fontconfig_number(field: &str) -> Option<u32> {
+        field
+            .trim_start_matches('[')
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    }
+
+    /// Convert a fontconfig weight to the 1 to 1000 scale.
+    fn fontconfig_weight(weight: u32) -> FontWeight {
+        FontWeight(match weight {
+            0..=39 => 100,
+            40..=49 => 200,
+            50..=74 => 300,
+            75..=89 => 400,
+            90..=139 => 500,
+            140..=189 => 600,
+            190..=202 => 700,
+            203..=207 => 800,
+            _ => 900,
+        })
+    }
+}
+
+#[cfg(all(feature = "std", not(unix)))]
+mod imp {
+    use super::FontInfo;
+    use crate::{Error, Result};
+    use alloc::vec::Vec;
+
+    pub(super) fn system_fonts() -> Result<Vec<FontInfo>> {
+        Err(Error::unsupported("system_fonts"))
+    }
+}
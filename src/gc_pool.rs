@@ -0,0 +1,220 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::Window;
+use alloc::collections::{btree_map::Entry, BTreeMap};
+
+/// The properties that decide whether windows can share a graphics
+/// context.
+///
+/// On X11, a GC can be used with any drawable of the same root, visual
+/// and depth.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GcKey {
+    /// The visual of the window.
+    pub visual: u32,
+    /// The depth of the window.
+    pub depth: u8,
+}
+
+impl GcKey {
+    /// Create a new `GcKey` from a visual and a depth.
+    pub fn new(visual: u32, depth: u8) -> Self {
+        GcKey { visual, depth }
+    }
+}
+
+/// A pool of graphics contexts shared between windows, for use by
+/// backends.
+///
+/// Rather than creating a GC for every window, backends keep one per
+/// `GcKey` here, shared by every window with that visual and depth. A
+/// GC is handed back to be freed as soon as the last window using it is
+/// released, e.g. because it was destroyed, rather than when the runtime
+/// shuts down.
+#[derive(Debug)]
+pub struct GcPool<T> {
+    gcs: BTreeMap<GcKey, PooledGc<T>>,
+    windows: BTreeMap<Window, GcKey>,
+}
+
+#[derive(Debug)]
+struct PooledGc<T> {
+    gc: T,
+    users: usize,
+}
+
+impl<T> Default for GcPool<T> {
+    fn default() -> Self {
+        GcPool {
+            gcs: BTreeMap::new(),
+            windows: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> GcPool<T> {
+    /// Create a new, empty `GcPool`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of graphics contexts in the pool.
+    pub fn len(&self) -> usize {
+        self.gcs.len()
+    }
+
+    /// Tell whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.gcs.is_empty()
+    }
+
+    /// Get the graphics context for a window with the given key,
+    /// creating it with `create` if no window with that key has one.
+    ///
+    /// If the window was using a GC with a different key, that GC is
+    /// released first, and returned if it should be freed.
+    pub fn acquire<E>(
+        &mut self,
+        window: Window,
+        key: GcKey,
+        create: impl FnOnce(GcKey) -> Result<T, E>,
+    ) -> Result<(&T, Option<T>), E> {
+        let mut freed = None;
+        if self.windows.get(&window) != Some(&key) {
+            freed = self.release(window);
+            let pooled = match self.gcs.entry(key) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(PooledGc {
+                    gc: create(key)?,
+                    users: 0,
+                }),
+            };
+            pooled.users += 1;
+            self.windows.insert(window, key);
+        }
+
+        Ok((&self.gcs[&key].gc, freed))
+    }
+
+    /// Stop using a graphics context for a window, returning the GC if
+    /// no other window uses it, so that it can be freed.
+    pub fn release(&mut self, window: Window) -> Option<T> {
+        let key = self.windows.remove(&window)?;
+        let pooled = self.gcs.get_mut(&key)?;
+        pooled.users -= 1;
+        if pooled.users == 0 {
+            self.gcs.remove(&key).map(|pooled| pooled.gc)
+        } else {
+            None
+        }
+    }
+
+    /// Remove and return every graphics context, so that they can be
+    /// freed, e.g. when the runtime shuts down.
+    ///
+    /// The number of GCs returned is reported as
+    /// `ShutdownReport::freed_gcs`.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> {
+        self.windows.clear();
+        core::mem::take(&mut self.gcs)
+            .into_values()
+            .map(|pooled| pooled.gc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        async_runtime::Runtime,
+        testing::{now, LogEntry, TestRuntime},
+        VisualPreference, WindowProps,
+    };
+    use alloc::vec::Vec;
+    use core::num::NonZeroUsize;
+
+    fn window(id: usize) -> Window {
+        Window::new(NonZeroUsize::new(id).unwrap())
+    }
+
+    fn gc_log(runtime: &mut TestRuntime) -> Vec<LogEntry> {
+        runtime
+            .take_log()
+            .into_iter()
+            .filter(|entry| matches!(entry, LogEntry::CreateGc(_) | LogEntry::FreeGc(_)))
+            .collect()
+    }
+
+    #[test]
+    fn shares_gcs_between_windows() {
+        let mut pool = GcPool::new();
+        let key = GcKey::new(1, 24);
+        let mut created = 0;
+        let mut create = |key| {
+            created += 1;
+            Ok::<_, ()>(key)
+        };
+
+        let (a, b) = (window(1), window(2));
+        assert_eq!(pool.acquire(a, key, &mut create).unwrap(), (&key, None));
+        assert_eq!(pool.acquire(b, key, &mut create).unwrap(), (&key, None));
+        assert_eq!(pool.len(), 1);
+
+        assert_eq!(pool.release(a), None);
+        assert_eq!(pool.release(b), Some(key));
+        assert!(pool.is_empty());
+        assert_eq!(created, 1);
+    }
+
+    #[test]
+    fn changing_key_releases_old_gc() {
+        let mut pool = GcPool::new();
+        let win = window(1);
+        let (old, new) = (GcKey::new(1, 24), GcKey::new(2, 32));
+        pool.acquire(win, old, Ok::<_, ()>).unwrap();
+        assert_eq!(
+            pool.acquire(win, new, Ok::<_, ()>).unwrap(),
+            (&new, Some(old))
+        );
+        assert_eq!(pool.drain().collect::<Vec<_>>(), [new]);
+    }
+
+    #[test]
+    fn runtime_frees_gcs_with_their_last_window() {
+        let mut runtime = TestRuntime::new();
+        let a = now(runtime.create_window(WindowProps::new())).unwrap();
+        let b = now(runtime.create_window(WindowProps::new())).unwrap();
+        let mut props = WindowProps::new();
+        props.visual_preference = VisualPreference::Depth(16);
+        let c = now(runtime.create_window(props)).unwrap();
+        for window in [a, b, c] {
+            now(runtime.window_present_with_damage(window, &[])).unwrap();
+        }
+        let (normal, shallow) = (GcKey::new(24, 24), GcKey::new(16, 16));
+        assert_eq!(
+            gc_log(&mut runtime),
+            [LogEntry::CreateGc(normal), LogEntry::CreateGc(shallow)]
+        );
+
+        now(runtime.destroy_window(a)).unwrap();
+        assert_eq!(gc_log(&mut runtime), []);
+        now(runtime.destroy_window(b)).unwrap();
+        assert_eq!(gc_log(&mut runtime), [LogEntry::FreeGc(normal)]);
+
+        assert_eq!(now(runtime.shutdown()).unwrap().freed_gcs, 1);
+    }
+}
@@ -0,0 +1,322 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use core::convert::TryFrom;
+
+/// An edge of a rectangle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Edge {
+    /// The left edge.
+    Left,
+    /// The top edge.
+    Top,
+    /// The right edge.
+    Right,
+    /// The bottom edge.
+    Bottom,
+}
+
+/// A corner of a rectangle.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Corner {
+    /// The top-left corner.
+    TopLeft,
+    /// The top-right corner.
+    TopRight,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+/// Where something is placed along one axis of the space it is given.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Alignment {
+    /// At the left or the top.
+    #[default]
+    Start,
+    /// In the middle.
+    Center,
+    /// At the right or the bottom.
+    End,
+}
+
+/// Distances inwards from each edge of a rectangle, e.g. a margin or
+/// padding.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Insets {
+    /// The distance from the left edge.
+    pub left: i32,
+    /// The distance from the top edge.
+    pub top: i32,
+    /// The distance from the right edge.
+    pub right: i32,
+    /// The distance from the bottom edge.
+    pub bottom: i32,
+}
+
+impl Insets {
+    /// Create a new `Insets`.
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Insets {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Create insets that are the same on every edge.
+    pub fn uniform(inset: i32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    /// Create insets with one distance for the left and right edges and
+    /// another for the top and bottom edges.
+    pub fn symmetric(horizontal: i32, vertical: i32) -> Self {
+        Self::new(horizontal, vertical, horizontal, vertical)
+    }
+
+    /// Get the inset from one edge.
+    pub fn get(&self, edge: Edge) -> i32 {
+        match edge {
+            Edge::Left => self.left,
+            Edge::Top => self.top,
+            Edge::Right => self.right,
+            Edge::Bottom => self.bottom,
+        }
+    }
+
+    /// Get the sum of the left and right insets.
+    pub fn horizontal(&self) -> i32 {
+        self.left + self.right
+    }
+
+    /// Get the sum of the top and bottom insets.
+    pub fn vertical(&self) -> i32 {
+        self.top + self.bottom
+    }
+}
+
+/// An axis-aligned rectangle.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Rectangle {
+    /// The X coordinate of the top-left corner.
+    pub x: i32,
+    /// The Y coordinate of the top-left corner.
+    pub y: i32,
+    /// The width of the rectangle.
+    pub width: u32,
+    /// The height of the rectangle.
+    pub height: u32,
+}
+
+impl Rectangle {
+    /// Create a new `Rectangle`.
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Tell whether the rectangle covers no area.
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Tell whether this rectangle overlaps or touches `other`.
+    pub fn touches(&self, other: &Rectangle) -> bool {
+        let (right, bottom) = self.far_corner();
+        let (other_right, other_bottom) = other.far_corner();
+        i64::from(self.x) <= other_right
+            && i64::from(other.x) <= right
+            && i64::from(self.y) <= other_bottom
+            && i64::from(other.y) <= bottom
+    }
+
+    /// Get the smallest rectangle containing both this rectangle and
+    /// `other`.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        if self.is_empty() {
+            return *other;
+        } else if other.is_empty() {
+            return *self;
+        }
+
+        let (right, bottom) = self.far_corner();
+        let (other_right, other_bottom) = other.far_corner();
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rectangle::new(
+            x,
+            y,
+            (right.max(other_right) - i64::from(x)) as u32,
+            (bottom.max(other_bottom) - i64::from(y)) as u32,
+        )
+    }
+
+    /// Tell whether the point `(x, y)` lies within this rectangle.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let (right, bottom) = self.far_corner();
+        x >= self.x && y >= self.y && i64::from(x) < right && i64::from(y) < bottom
+    }
+
+    /// Tell whether `other` lies entirely within this rectangle.
+    pub fn contains_rect(&self, other: &Rectangle) -> bool {
+        let (right, bottom) = self.far_corner();
+        let (other_right, other_bottom) = other.far_corner();
+        other.x >= self.x && other.y >= self.y && other_right <= right && other_bottom <= bottom
+    }
+
+    /// Get the area shared by this rectangle and `other`, or `None` if
+    /// they do not overlap.
+    pub fn intersect(&self, other: &Rectangle) -> Option<Rectangle> {
+        let (right, bottom) = self.far_corner();
+        let (other_right, other_bottom) = other.far_corner();
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let width = right.min(other_right) - i64::from(x);
+        let height = bottom.min(other_bottom) - i64::from(y);
+
+        if width > 0 && height > 0 {
+            Some(Rectangle::new(x, y, width as u32, height as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Shrink this rectangle by `insets`, or grow it if they are
+    /// negative.
+    ///
+    /// The size stops at zero rather than going negative.
+    pub fn inset(&self, insets: Insets) -> Rectangle {
+        let width = i64::from(self.width) - i64::from(insets.horizontal());
+        let height = i64::from(self.height) - i64::from(insets.vertical());
+        Rectangle::new(
+            self.x.saturating_add(insets.left),
+            self.y.saturating_add(insets.top),
+            clamp_size(width),
+            clamp_size(height),
+        )
+    }
+
+    /// Split a strip of `amount` pixels off of one edge, returning the
+    /// strip and the rest of the rectangle.
+    ///
+    /// The strip is no larger than the rectangle itself.
+    pub fn split(&self, edge: Edge, amount: u32) -> (Rectangle, Rectangle) {
+        let Rectangle {
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+
+        match edge {
+            Edge::Left => {
+                let amount = amount.min(width);
+                (
+                    Rectangle::new(x, y, amount, height),
+                    Rectangle::new(offset(x, amount), y, width - amount, height),
+                )
+            }
+            Edge::Right => {
+                let amount = amount.min(width);
+                (
+                    Rectangle::new(offset(x, width - amount), y, amount, height),
+                    Rectangle::new(x, y, width - amount, height),
+                )
+            }
+            Edge::Top => {
+                let amount = amount.min(height);
+                (
+                    Rectangle::new(x, y, width, amount),
+                    Rectangle::new(x, offset(y, amount), width, height - amount),
+                )
+            }
+            Edge::Bottom => {
+                let amount = amount.min(height);
+                (
+                    Rectangle::new(x, offset(y, height - amount), width, amount),
+                    Rectangle::new(x, y, width, height - amount),
+                )
+            }
+        }
+    }
+
+    /// Get the coordinates of one of the rectangle's corners.
+    ///
+    /// The right and bottom corners lie just outside of the rectangle.
+    pub fn corner(&self, corner: Corner) -> (i32, i32) {
+        let right = offset(self.x, self.width);
+        let bottom = offset(self.y, self.height);
+        match corner {
+            Corner::TopLeft => (self.x, self.y),
+            Corner::TopRight => (right, self.y),
+            Corner::BottomLeft => (self.x, bottom),
+            Corner::BottomRight => (right, bottom),
+        }
+    }
+
+    /// Place a rectangle of the given size within this one.
+    pub fn align(
+        &self,
+        width: u32,
+        height: u32,
+        horizontal: Alignment,
+        vertical: Alignment,
+    ) -> Rectangle {
+        let place = |start: i32, space: u32, size: u32, alignment: Alignment| {
+            let free = i64::from(space) - i64::from(size);
+            let shift = match alignment {
+                Alignment::Start => 0,
+                Alignment::Center => free / 2,
+                Alignment::End => free,
+            };
+            i32::try_from(i64::from(start) + shift).unwrap_or(start)
+        };
+
+        Rectangle::new(
+            place(self.x, self.width, width, horizontal),
+            place(self.y, self.height, height, vertical),
+            width,
+            height,
+        )
+    }
+
+    /// Get the coordinates just past the bottom-right corner.
+    fn far_corner(&self) -> (i64, i64) {
+        (
+            i64::from(self.x) + i64::from(self.width),
+            i64::from(self.y) + i64::from(self.height),
+        )
+    }
+}
+
+/// Move a coordinate along by a size, saturating at the edge of the
+/// coordinate space.
+fn offset(start: i32, size: u32) -> i32 {
+    i32::try_from(i64::from(start) + i64::from(size)).unwrap_or(i32::MAX)
+}
+
+/// Clamp a computed size to the range of a `u32`.
+fn clamp_size(size: i64) -> u32 {
+    u32::try_from(size.max(0)).unwrap_or(u32::MAX)
+}
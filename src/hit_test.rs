@@ -0,0 +1,92 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::{ResizeEdge, Window};
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::fmt;
+
+/// What part of a window's chrome lies under a point, as decided by a
+/// `HitTestHandler`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HitTestResult {
+    /// The client area, which receives mouse events as usual.
+    Client,
+    /// The title bar, which moves the window when dragged.
+    Caption,
+    /// A border, which resizes the window when dragged.
+    ResizeBorder(ResizeEdge),
+    /// The system menu button, which opens the window menu when clicked.
+    SystemMenu,
+}
+
+/// A function that tells what part of a window lies under a point,
+/// relative to the window.
+///
+/// This lets windows that draw all of their own chrome behave like
+/// native ones. It answers `WM_NCHITTEST` on Windows; on X11, it is
+/// emulated by starting an interactive move or resize when a button is
+/// pressed over the caption or a border.
+pub type HitTestHandler = Box<dyn FnMut(i32, i32) -> HitTestResult + Send + 'static>;
+
+/// Keeps track of the hit test handlers of each window.
+///
+/// This is intended for use by backends.
+#[derive(Default)]
+pub struct HitTestMap {
+    handlers: BTreeMap<Window, HitTestHandler>,
+}
+
+impl fmt::Debug for HitTestMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HitTestMap")
+            .field("handlers", &self.handlers.keys())
+            .finish()
+    }
+}
+
+impl HitTestMap {
+    /// Create a new, empty `HitTestMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set or remove the hit test handler for a window.
+    pub fn set_handler(&mut self, window: Window, handler: Option<HitTestHandler>) {
+        match handler {
+            Some(handler) => {
+                self.handlers.insert(window, handler);
+            }
+            None => {
+                self.handlers.remove(&window);
+            }
+        }
+    }
+
+    /// Remove every handler.
+    pub fn clear(&mut self) {
+        self.handlers.clear();
+    }
+
+    /// Tell what part of a window lies under a point.
+    ///
+    /// Windows without a handler are client area throughout.
+    pub fn test(&mut self, window: Window, x: i32, y: i32) -> HitTestResult {
+        match self.handlers.get_mut(&window) {
+            Some(handler) => handler(x, y),
+            None => HitTestResult::Client,
+        }
+    }
+}
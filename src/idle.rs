@@ -0,0 +1,135 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// A function that performs a small amount of background work while the
+/// event loop is idle.
+///
+/// It is called repeatedly until it returns `IdleStatus::Done`, so each
+/// call should only do a slice of the work, such as laying out one
+/// paragraph.
+pub type IdleCallback = Box<dyn FnMut() -> IdleStatus + Send + 'static>;
+
+/// The number of idle callbacks a runtime runs each time its event queue
+/// runs dry, before it goes back to waiting for events.
+pub const DEFAULT_IDLE_BUDGET: usize = 16;
+
+/// Whether an idle callback has more work to do.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IdleStatus {
+    /// Call the callback again the next time the event loop is idle.
+    Continue,
+    /// The work is finished; remove the callback.
+    Done,
+}
+
+/// The ID of a callback registered with `Runtime::on_idle`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IdleId(u64);
+
+/// Runs idle callbacks in turn, within a budget.
+///
+/// This is intended for use by backends, which call `run` when the event
+/// queue is empty, right before blocking. Callbacks take turns, so one
+/// long job cannot starve the others.
+#[derive(Default)]
+pub struct IdleQueue {
+    callbacks: Vec<(IdleId, IdleCallback)>,
+    /// The index of the callback that runs next.
+    cursor: usize,
+    next_id: u64,
+}
+
+impl fmt::Debug for IdleQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdleQueue")
+            .field(
+                "callbacks",
+                &self.callbacks.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            )
+            .field("cursor", &self.cursor)
+            .finish()
+    }
+}
+
+impl IdleQueue {
+    /// Create a new, empty `IdleQueue`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a callback to the queue.
+    pub fn add(&mut self, callback: IdleCallback) -> IdleId {
+        let id = IdleId(self.next_id);
+        self.next_id += 1;
+        self.callbacks.push((id, callback));
+        id
+    }
+
+    /// Remove a callback from the queue, returning whether it was there.
+    pub fn remove(&mut self, id: IdleId) -> bool {
+        match self.callbacks.iter().position(|(i, _)| *i == id) {
+            Some(index) => {
+                self.remove_at(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) {
+        drop(self.callbacks.remove(index));
+        if index < self.cursor {
+            self.cursor -= 1;
+        }
+    }
+
+    /// Tell whether there are no callbacks in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Get the number of callbacks in the queue.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Remove every callback.
+    pub fn clear(&mut self) {
+        self.callbacks.clear();
+        self.cursor = 0;
+    }
+
+    /// Run up to `budget` callbacks, returning how many were run.
+    pub fn run(&mut self, budget: usize) -> usize {
+        let mut ran = 0;
+        while ran < budget && !self.callbacks.is_empty() {
+            if self.cursor >= self.callbacks.len() {
+                self.cursor = 0;
+            }
+
+            let index = self.cursor;
+            ran += 1;
+            match (self.callbacks[index].1)() {
+                IdleStatus::Continue => self.cursor += 1,
+                IdleStatus::Done => self.remove_at(index),
+            }
+        }
+        ran
+    }
+}
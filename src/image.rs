@@ -0,0 +1,118 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// An image, stored as 8-bit RGBA pixels in row-major order.
+///
+/// Images cannot be modified once created, so cloning one is cheap:
+/// clones share the same pixels and identifier, and backends only need
+/// to upload an image to the server once. See `ImageCache`.
+#[derive(Debug, Clone)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Arc<[u8]>,
+    key: Arc<ImageKey>,
+}
+
+/// The identity of an image, shared by all of its clones.
+///
+/// Caches hold weak references to it, to notice when every clone of an
+/// image has been dropped.
+#[derive(Debug)]
+pub(crate) struct ImageKey(usize);
+
+impl Image {
+    /// Create a new image from its dimensions and its pixel data.
+    ///
+    /// Returns `None` if `pixels` does not contain exactly
+    /// `width * height * 4` bytes.
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(4)?;
+
+        if pixels.len() == len {
+            static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+            Some(Image {
+                width,
+                height,
+                pixels: pixels.into(),
+                key: Arc::new(ImageKey(NEXT_ID.fetch_add(1, Ordering::Relaxed))),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the identifier of this image, which is shared by its clones
+    /// and unique among images.
+    pub fn id(&self) -> usize {
+        self.key.0
+    }
+
+    /// Get the key shared by this image's clones.
+    pub(crate) fn key(&self) -> &Arc<ImageKey> {
+        &self.key
+    }
+
+    /// Get the width of the image.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the image.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the pixel data of the image.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Convert this image into its pixel data.
+    ///
+    /// This copies the pixels, since clones of the image may share them.
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels.to_vec()
+    }
+}
+
+// images are compared by their contents, not their identity
+
+impl PartialEq for Image {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.pixels == other.pixels
+    }
+}
+
+impl Eq for Image {}
+
+impl Hash for Image {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.pixels.hash(state);
+    }
+}
@@ -0,0 +1,263 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::{image::ImageKey, Image};
+use alloc::{
+    collections::BTreeMap,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+
+/// The number of bytes of images that backends keep on the server by
+/// default.
+pub const DEFAULT_IMAGE_CACHE_BUDGET: usize = 64 * 1024 * 1024;
+
+/// A cache of images uploaded to the server, for use by backends.
+///
+/// Uploading an image, e.g. into an X11 pixmap or a Win32 bitmap, is
+/// expensive, so backends keep the server-side resource here, keyed by
+/// the image's identifier. Once the cached images exceed the byte
+/// budget, the least recently used ones are evicted, and images are
+/// evicted as soon as every clone of their source `Image` has been
+/// dropped.
+///
+/// Evicted resources are not freed by the cache: the backend takes them
+/// with `drain_evicted` and frees them on the server.
+#[derive(Debug)]
+pub struct ImageCache<T> {
+    entries: BTreeMap<usize, CachedImage<T>>,
+    evicted: Vec<T>,
+    budget: usize,
+    bytes: usize,
+    /// Incremented on every use, to order entries by recency.
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct CachedImage<T> {
+    resource: T,
+    bytes: usize,
+    last_used: u64,
+    source: Weak<ImageKey>,
+}
+
+impl<T> Default for ImageCache<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_IMAGE_CACHE_BUDGET)
+    }
+}
+
+impl<T> ImageCache<T> {
+    /// Create a new, empty `ImageCache` that holds at most `budget`
+    /// bytes of images.
+    pub fn new(budget: usize) -> Self {
+        ImageCache {
+            entries: BTreeMap::new(),
+            evicted: Vec::new(),
+            budget,
+            bytes: 0,
+            clock: 0,
+        }
+    }
+
+    /// Get the number of bytes of images the cache may hold.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Set the number of bytes of images the cache may hold, evicting
+    /// images until it fits.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict(0);
+    }
+
+    /// Get the number of bytes of images in the cache.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Get the number of images in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Tell whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the server-side resource for `image`, uploading it with
+    /// `create` if it is not already cached.
+    ///
+    /// An image larger than the whole budget is still cached, after
+    /// evicting everything else.
+    pub fn get_or_create<E>(
+        &mut self,
+        image: &Image,
+        create: impl FnOnce(&Image) -> Result<T, E>,
+    ) -> Result<&T, E> {
+        self.clock += 1;
+        let id = image.id();
+
+        if !self.entries.contains_key(&id) {
+            let resource = create(image)?;
+            let bytes = image.pixels().len();
+            self.sweep();
+            self.evict(bytes);
+            self.bytes += bytes;
+            self.entries.insert(
+                id,
+                CachedImage {
+                    resource,
+                    bytes,
+                    last_used: 0,
+                    source: Arc::downgrade(image.key()),
+                },
+            );
+        }
+
+        let entry = self.entries.get_mut(&id).unwrap();
+        entry.last_used = self.clock;
+        Ok(&entry.resource)
+    }
+
+    /// Evict the images whose source `Image` has been dropped.
+    pub fn sweep(&mut self) {
+        let dropped: Vec<usize> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.source.strong_count() == 0)
+            .map(|(&id, _)| id)
+            .collect();
+
+        for id in dropped {
+            self.remove(id);
+        }
+    }
+
+    /// Evict every image.
+    pub fn purge(&mut self) {
+        let ids: Vec<usize> = self.entries.keys().copied().collect();
+        for id in ids {
+            self.remove(id);
+        }
+    }
+
+    /// Remove and return the resources of evicted images, so that they
+    /// can be freed.
+    pub fn drain_evicted(&mut self) -> impl Iterator<Item = T> {
+        core::mem::take(&mut self.evicted).into_iter()
+    }
+
+    /// Evict the least recently used images until `incoming` more bytes
+    /// fit in the budget.
+    fn evict(&mut self, incoming: usize) {
+        while self.bytes.saturating_add(incoming) > self.budget {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(&id, _)| id);
+
+            match oldest {
+                Some(id) => self.remove(id),
+                None => break,
+            }
+        }
+    }
+
+    fn remove(&mut self, id: usize) {
+        if let Some(entry) = self.entries.remove(&id) {
+            self.bytes -= entry.bytes;
+            self.evicted.push(entry.resource);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        async_runtime::Runtime,
+        testing::{now, LogEntry, TestRuntime},
+        Cursor, WindowProps,
+    };
+    use core::convert::Infallible;
+
+    fn pixel() -> Image {
+        Image::new(1, 1, alloc::vec![0; 4]).unwrap()
+    }
+
+    fn upload(cache: &mut ImageCache<usize>, image: &Image) {
+        cache
+            .get_or_create(image, |image| Ok::<_, Infallible>(image.id()))
+            .unwrap();
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ImageCache::new(8);
+        let (a, b, c) = (pixel(), pixel(), pixel());
+        upload(&mut cache, &a);
+        upload(&mut cache, &b);
+        upload(&mut cache, &a);
+        upload(&mut cache, &c);
+
+        assert_eq!(cache.drain_evicted().collect::<Vec<_>>(), [b.id()]);
+        assert_eq!(cache.bytes(), 8);
+    }
+
+    #[test]
+    fn evicts_dropped_images() {
+        let mut cache = ImageCache::new(DEFAULT_IMAGE_CACHE_BUDGET);
+        let image = pixel();
+        let id = image.id();
+        upload(&mut cache, &image.clone());
+        cache.sweep();
+        assert!(cache.drain_evicted().next().is_none());
+
+        drop(image);
+        cache.sweep();
+        assert_eq!(cache.drain_evicted().collect::<Vec<_>>(), [id]);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn runtime_purges_uploaded_images() {
+        let mut runtime = TestRuntime::new();
+        let window = now(runtime.create_window(WindowProps::new())).unwrap();
+        let image = pixel();
+        let cursor = Cursor::from_image(&image, (0, 0));
+        let images = |runtime: &mut TestRuntime| {
+            runtime
+                .take_log()
+                .into_iter()
+                .filter(|entry| matches!(entry, LogEntry::UploadImage(_) | LogEntry::FreeImage(_)))
+                .collect::<Vec<_>>()
+        };
+
+        now(runtime.window_set_cursor(window, cursor.clone())).unwrap();
+        now(runtime.window_set_cursor(window, cursor.clone())).unwrap();
+        assert_eq!(images(&mut runtime), [LogEntry::UploadImage(image.id())]);
+
+        now(runtime.purge_image_cache()).unwrap();
+        assert_eq!(images(&mut runtime), [LogEntry::FreeImage(image.id())]);
+
+        now(runtime.window_set_cursor(window, cursor)).unwrap();
+        assert_eq!(images(&mut runtime), [LogEntry::UploadImage(image.id())]);
+    }
+}
@@ -0,0 +1,168 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use core::ops::{BitOr, BitOrAssign};
+
+/// A set of keyboard modifiers.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    /// No modifiers.
+    pub const NONE: Modifiers = Modifiers(0);
+    /// The shift key.
+    pub const SHIFT: Modifiers = Modifiers(1 << 0);
+    /// The control key.
+    pub const CONTROL: Modifiers = Modifiers(1 << 1);
+    /// The alt key.
+    pub const ALT: Modifiers = Modifiers(1 << 2);
+    /// The super key, also known as the Windows or Command key.
+    pub const SUPER: Modifiers = Modifiers(1 << 3);
+
+    /// Tell whether all of the modifiers in `other` are set in this one.
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Tell whether no modifiers are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Get the raw bits of this set of modifiers.
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Create a set of modifiers from raw bits, ignoring unknown bits.
+    pub fn from_bits_truncate(bits: u8) -> Self {
+        Modifiers(bits & 0b1111)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Modifiers) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A key on the keyboard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Key {
+    /// A key that produces a character.
+    Char(char),
+    /// A function key, e.g. `Function(1)` for F1.
+    Function(u8),
+    /// The space bar.
+    Space,
+    /// The escape key.
+    Escape,
+    /// The enter or return key.
+    Enter,
+    /// The tab key.
+    Tab,
+    /// The backspace key.
+    Backspace,
+    /// The delete key.
+    Delete,
+    /// The insert key.
+    Insert,
+    /// The home key.
+    Home,
+    /// The end key.
+    End,
+    /// The page up key.
+    PageUp,
+    /// The page down key.
+    PageDown,
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The left arrow key.
+    Left,
+    /// The right arrow key.
+    Right,
+}
+
+/// A keyboard mapping that is built when it is first needed, and rebuilt
+/// after it changes.
+///
+/// This is intended for use by backends, which keep their translation
+/// from key codes to keys here, e.g. the X11 keyboard state. When the
+/// mapping changes, such as on `MappingNotify` or an XKB
+/// `NewKeyboardNotify` on X11 or `WM_INPUTLANGCHANGE` on Windows, the
+/// backend calls `invalidate` and delivers `EventType::KeymapChanged`.
+#[derive(Debug, Clone)]
+pub struct KeymapCache<T> {
+    keymap: Option<T>,
+}
+
+impl<T> Default for KeymapCache<T> {
+    fn default() -> Self {
+        KeymapCache { keymap: None }
+    }
+}
+
+impl<T> KeymapCache<T> {
+    /// Create a new `KeymapCache` with no mapping built yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the keyboard mapping, building it with `build` if it has not
+    /// been built since the last change.
+    pub fn get_or_build<E>(&mut self, build: impl FnOnce() -> Result<T, E>) -> Result<&mut T, E> {
+        if self.keymap.is_none() {
+            self.keymap = Some(build()?);
+        }
+
+        Ok(self.keymap.as_mut().unwrap())
+    }
+
+    /// Discard the keyboard mapping, so that it is rebuilt the next time
+    /// it is needed, returning the old mapping if it had been built.
+    pub fn invalidate(&mut self) -> Option<T> {
+        self.keymap.take()
+    }
+}
+
+/// A key combined with the modifiers held along with it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyInfo {
+    /// The key that was pressed.
+    pub key: Key,
+    /// The modifiers held while the key was pressed.
+    pub modifiers: Modifiers,
+}
+
+impl KeyInfo {
+    /// Create a new `KeyInfo`.
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        KeyInfo { key, modifiers }
+    }
+}
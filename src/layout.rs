@@ -0,0 +1,236 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{Rectangle, Window};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// A length, either absolute or relative to the size of the parent.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Length {
+    /// A length in pixels.
+    Pixels(i32),
+    /// A fraction of the parent's size, where `1.0` is the full size.
+    Fraction(f32),
+    /// A fraction of the parent's size, plus a number of pixels.
+    ///
+    /// For instance, `Relative { fraction: 1.0, offset: -20 }` is 20
+    /// pixels short of the parent's full size.
+    Relative {
+        /// The fraction of the parent's size.
+        fraction: f32,
+        /// The number of pixels to add to it.
+        offset: i32,
+    },
+}
+
+impl Length {
+    /// Resolve this length against the size of the parent.
+    pub fn resolve(self, parent: u32) -> i32 {
+        match self {
+            Length::Pixels(pixels) => pixels,
+            Length::Fraction(fraction) => (parent as f32 * fraction) as i32,
+            Length::Relative { fraction, offset } => (parent as f32 * fraction) as i32 + offset,
+        }
+    }
+
+    /// Split this length into a fraction of the parent's size and a
+    /// number of pixels.
+    fn parts(self) -> (f32, i32) {
+        match self {
+            Length::Pixels(pixels) => (0.0, pixels),
+            Length::Fraction(fraction) => (fraction, 0),
+            Length::Relative { fraction, offset } => (fraction, offset),
+        }
+    }
+}
+
+/// The edges of the parent that a child window is anchored to, along with
+/// the margin to keep between the child and each edge.
+///
+/// An edge that is `None` is not anchored. A child anchored to neither
+/// edge along an axis is centered along it; a child anchored to both is
+/// stretched between them.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Anchors {
+    /// The margin from the parent's left edge.
+    pub left: Option<i32>,
+    /// The margin from the parent's top edge.
+    pub top: Option<i32>,
+    /// The margin from the parent's right edge.
+    pub right: Option<i32>,
+    /// The margin from the parent's bottom edge.
+    pub bottom: Option<i32>,
+}
+
+impl Anchors {
+    /// Anchor to every edge with the same margin, filling the parent.
+    pub fn fill(margin: i32) -> Self {
+        Anchors {
+            left: Some(margin),
+            top: Some(margin),
+            right: Some(margin),
+            bottom: Some(margin),
+        }
+    }
+}
+
+/// Compute the position and size of a child along one axis.
+fn anchor_axis(start: Option<i32>, end: Option<i32>, size: Length) -> (Length, Length) {
+    let (fraction, offset) = size.parts();
+    match (start, end) {
+        (Some(start), Some(end)) => (
+            Length::Pixels(start),
+            Length::Relative {
+                fraction: 1.0,
+                offset: -(start + end),
+            },
+        ),
+        (Some(start), None) => (Length::Pixels(start), size),
+        (None, Some(end)) => (
+            Length::Relative {
+                fraction: 1.0 - fraction,
+                offset: -(end + offset),
+            },
+            size,
+        ),
+        (None, None) => (
+            Length::Relative {
+                fraction: (1.0 - fraction) / 2.0,
+                offset: -offset / 2,
+            },
+            size,
+        ),
+    }
+}
+
+/// Constraints describing where a child window is placed within its
+/// parent.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChildLayout {
+    /// The X coordinate of the child, relative to the parent.
+    pub x: Length,
+    /// The Y coordinate of the child, relative to the parent.
+    pub y: Length,
+    /// The width of the child.
+    pub width: Length,
+    /// The height of the child.
+    pub height: Length,
+}
+
+impl ChildLayout {
+    /// Create a layout that anchors the child to the given edges of its
+    /// parent.
+    ///
+    /// `width` and `height` are ignored along axes where the child is
+    /// anchored to both edges.
+    pub fn anchored(anchors: Anchors, width: Length, height: Length) -> Self {
+        let (x, width) = anchor_axis(anchors.left, anchors.right, width);
+        let (y, height) = anchor_axis(anchors.top, anchors.bottom, height);
+        ChildLayout {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Compute the geometry of the child, given the size of its parent.
+    pub fn resolve(&self, parent_width: u32, parent_height: u32) -> Rectangle {
+        Rectangle::new(
+            self.x.resolve(parent_width),
+            self.y.resolve(parent_height),
+            self.width.resolve(parent_width).max(1) as u32,
+            self.height.resolve(parent_height).max(1) as u32,
+        )
+    }
+}
+
+/// Tracks the layouts attached to child windows, and computes their new
+/// geometry when their parent is resized.
+///
+/// This is intended for use by backends, which apply the computed
+/// geometry before delivering the parent's `EventType::Resized` event.
+#[derive(Debug, Clone, Default)]
+pub struct AutoLayout {
+    enabled: bool,
+    children: BTreeMap<Window, Vec<(Window, ChildLayout)>>,
+}
+
+impl AutoLayout {
+    /// Create a new, disabled `AutoLayout`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable automatic layout.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Tell whether automatic layout is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Attach a layout to `child`, which belongs to `parent`, or detach
+    /// it if `layout` is `None`.
+    pub fn set_layout(&mut self, parent: Window, child: Window, layout: Option<ChildLayout>) {
+        let children = self.children.entry(parent).or_default();
+        children.retain(|&(c, _)| c != child);
+
+        match layout {
+            Some(layout) => children.push((child, layout)),
+            None if children.is_empty() => {
+                self.children.remove(&parent);
+            }
+            None => {}
+        }
+    }
+
+    /// Forget about a window, whether it is a parent or a child.
+    pub fn remove(&mut self, window: Window) {
+        self.children.remove(&window);
+        self.remove_child(window);
+    }
+
+    /// Detach the layout of a child window, e.g. because it was moved to
+    /// another parent.
+    pub fn remove_child(&mut self, child: Window) {
+        for children in self.children.values_mut() {
+            children.retain(|&(c, _)| c != child);
+        }
+        self.children.retain(|_, children| !children.is_empty());
+    }
+
+    /// Compute the new geometry of every child of `parent`, given its
+    /// new size.
+    ///
+    /// Returns nothing if automatic layout is disabled.
+    pub fn on_resized(
+        &self,
+        parent: Window,
+        width: u32,
+        height: u32,
+    ) -> impl Iterator<Item = (Window, Rectangle)> + '_ {
+        self.children
+            .get(&parent)
+            .filter(|_| self.enabled)
+            .into_iter()
+            .flatten()
+            .map(move |(child, layout)| (*child, layout.resolve(width, height)))
+    }
+}
@@ -29,9 +29,92 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod accessibility;
+pub mod animation;
 pub mod async_runtime;
+pub mod color;
+pub mod font;
+pub mod testing;
 
+cfg_std! {
+    pub mod dialogs;
+
+    mod paint_pool;
+    pub use paint_pool::{BandPainter, PaintPool};
+}
+
+mod capabilities;
+pub use capabilities::Capabilities;
+mod caret;
+pub use caret::{CaretBlinker, DEFAULT_BLINK_INTERVAL};
+mod clipboard;
+pub use clipboard::{ClipboardContent, ClipboardFormat, Selection};
+mod connection;
+pub use connection::{recreation_order, ReconnectPolicy};
+mod control;
+pub use control::{ControlCommand, ControlKind};
+mod cursor;
+pub use cursor::{Cursor, CursorCache, CustomCursor};
+mod dpi;
+pub use dpi::{
+    DpiAwareness, LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, BASE_DPI,
+};
 mod error;
-pub use error::{Error, Result};
+pub use error::{Error, ErrorKind, Result};
+pub mod event;
+pub use event::{
+    ClickTracker, CrossingTracker, Event, EventDelivery, EventMask, EventQueue, EventType,
+    HoverConfig, HoverTracker, MouseButton, QueuePolicy, DEFAULT_DOUBLE_CLICK_INTERVAL,
+};
+mod gc_pool;
+pub use gc_pool::{GcKey, GcPool};
+mod geometry;
+pub use geometry::{Alignment, Corner, Edge, Insets, Rectangle};
+mod hit_test;
+pub use hit_test::{HitTestHandler, HitTestMap, HitTestResult};
+mod idle;
+pub use idle::{IdleCallback, IdleId, IdleQueue, IdleStatus, DEFAULT_IDLE_BUDGET};
+mod image;
+pub use image::Image;
+mod image_cache;
+pub use image_cache::{ImageCache, DEFAULT_IMAGE_CACHE_BUDGET};
+mod keyboard;
+pub use keyboard::{Key, KeyInfo, KeymapCache, Modifiers};
+mod layout;
+pub use layout::{Anchors, AutoLayout, ChildLayout, Length};
+mod menu;
+pub use menu::{Menu, MenuEntry, MenuItem, MenuItemId};
+mod metrics;
+pub use metrics::{Metrics, MetricsRecorder};
+mod native_event;
+pub use native_event::{
+    ClientMessage, ClientMessageData, NativeEvent, NativeEventFilter, NativeEventFilterId,
+    NativeEventFilters, NativeFilterAction, RawEvent,
+};
+mod native_handle;
+pub use native_handle::NativeHandle;
+mod paint;
+pub use paint::{ExposeDispatcher, InvalidationBatch, PacingPolicy, PaintHandler, PaintStats};
+mod registry;
+pub use registry::WindowRegistry;
+mod screen;
+pub use screen::ScreenInfo;
+mod shortcut;
+pub use shortcut::{ShortcutId, ShortcutMap, ShortcutScope};
+mod text_property;
+pub use text_property::{TextEncoding, TextProperty};
+mod text_layout;
+pub use text_layout::{
+    FontMetrics, LayoutLine, PositionedGlyph, StyledRun, TextLayout, TextSpan, TextStyle,
+};
+mod theme;
+pub use theme::Theme;
+mod video;
+pub use video::{ImageFormat, VideoFrame};
 mod window;
-pub use window::Window;
+pub use window::{
+    AttentionType, Background, CornerPreference, ResizeEdge, UserData, Visibility, VisualInfo,
+    VisualPreference, Window, WindowConfig, WindowInfo, WindowProps, WindowState,
+};
+mod wm;
+pub use wm::{WmInfo, WmQuirks};
@@ -0,0 +1,133 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::KeyInfo;
+use alloc::{string::String, vec::Vec};
+
+/// A menu, used either as a menu bar or as a context menu.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Menu {
+    entries: Vec<MenuEntry>,
+}
+
+/// An entry in a `Menu`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MenuEntry {
+    /// An item that can be activated.
+    Item(MenuItem),
+    /// A labeled submenu.
+    Submenu(String, Menu),
+    /// A separator between groups of entries.
+    Separator,
+}
+
+/// An item in a menu that can be activated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MenuItem {
+    /// The identifier reported when this item is activated.
+    pub id: MenuItemId,
+    /// The label displayed for this item.
+    pub label: String,
+    /// The keyboard accelerator that activates this item, if any.
+    pub accelerator: Option<KeyInfo>,
+    /// Whether or not this item can be activated.
+    pub enabled: bool,
+}
+
+/// The identifier for a menu item.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct MenuItemId(u32);
+
+impl MenuItemId {
+    /// Create a new menu item identifier.
+    pub fn new(id: u32) -> Self {
+        MenuItemId(id)
+    }
+
+    /// Get the inner identifier.
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+impl Menu {
+    /// Create a new, empty `Menu`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item to the end of this menu.
+    pub fn item(&mut self, id: MenuItemId, label: impl Into<String>) -> &mut Self {
+        self.entries.push(MenuEntry::Item(MenuItem::new(id, label)));
+        self
+    }
+
+    /// Add an item with a keyboard accelerator to the end of this menu.
+    pub fn item_with_accelerator(
+        &mut self,
+        id: MenuItemId,
+        label: impl Into<String>,
+        accelerator: KeyInfo,
+    ) -> &mut Self {
+        let mut item = MenuItem::new(id, label);
+        item.accelerator = Some(accelerator);
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    /// Add a submenu to the end of this menu.
+    pub fn submenu(&mut self, label: impl Into<String>, menu: Menu) -> &mut Self {
+        self.entries.push(MenuEntry::Submenu(label.into(), menu));
+        self
+    }
+
+    /// Add a separator to the end of this menu.
+    pub fn separator(&mut self) -> &mut Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Get the entries in this menu.
+    pub fn entries(&self) -> &[MenuEntry] {
+        &self.entries
+    }
+
+    /// Find the item that the given key combination activates, searching
+    /// submenus as well.
+    pub fn find_accelerator(&self, key: &KeyInfo) -> Option<&MenuItem> {
+        self.entries.iter().find_map(|entry| match entry {
+            MenuEntry::Item(item) if item.enabled && item.accelerator.as_ref() == Some(key) => {
+                Some(item)
+            }
+            MenuEntry::Submenu(_, menu) => menu.find_accelerator(key),
+            _ => None,
+        })
+    }
+}
+
+impl MenuItem {
+    /// Create a new, enabled `MenuItem` without an accelerator.
+    pub fn new(id: MenuItemId, label: impl Into<String>) -> Self {
+        MenuItem {
+            id,
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::Window;
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+
+/// Counters describing the work done by a runtime's event loop, e.g. for
+/// a diagnostics overlay.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Metrics {
+    /// The number of events delivered by `next_event`.
+    pub events_processed: u64,
+    /// The number of times a window was painted and presented.
+    pub paints: u64,
+    /// The total time spent painting, from an exposure or redraw being
+    /// delivered to the window being presented.
+    pub paint_time: Duration,
+    /// The number of events waiting to be delivered.
+    pub queue_depth: usize,
+}
+
+impl Metrics {
+    /// Get the average time spent on each paint, or `None` if nothing has
+    /// been painted yet.
+    pub fn average_paint_duration(&self) -> Option<Duration> {
+        if self.paints == 0 {
+            return None;
+        }
+
+        let nanos = self.paint_time.as_nanos() / u128::from(self.paints);
+        Some(Duration::from_nanos(nanos.min(u128::from(u64::MAX)) as u64))
+    }
+}
+
+/// Keeps the `Metrics` of a runtime up to date.
+///
+/// This is intended for use by backends, which call `record_event` for
+/// each event they deliver, `begin_paint` when they deliver an exposure
+/// or redraw, and `end_paint` when the window is presented. Times are
+/// taken from the backend's monotonic clock.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRecorder {
+    metrics: Metrics,
+    /// When each window's pending paint started.
+    paints: BTreeMap<Window, Duration>,
+}
+
+impl MetricsRecorder {
+    /// Create a new `MetricsRecorder` with every counter at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count an event as delivered.
+    pub fn record_event(&mut self) {
+        self.metrics.events_processed += 1;
+    }
+
+    /// Note that a window started painting at `now`.
+    ///
+    /// If the window is already painting, e.g. because several exposures
+    /// were delivered before it presented, the earliest start is kept.
+    pub fn begin_paint(&mut self, window: Window, now: Duration) {
+        self.paints.entry(window).or_insert(now);
+    }
+
+    /// Note that a window finished painting at `now`.
+    ///
+    /// Paints that were never begun, e.g. because the application
+    /// presented on its own initiative, are counted without a duration.
+    pub fn end_paint(&mut self, window: Window, now: Duration) {
+        self.metrics.paints += 1;
+        if let Some(start) = self.paints.remove(&window) {
+            self.metrics.paint_time += now.checked_sub(start).unwrap_or_default();
+        }
+    }
+
+    /// Forget a window's pending paint, e.g. because it was destroyed.
+    pub fn remove(&mut self, window: Window) {
+        self.paints.remove(&window);
+    }
+
+    /// Get the current counters, given the number of events that are
+    /// waiting to be delivered.
+    pub fn metrics(&self, queue_depth: usize) -> Metrics {
+        Metrics {
+            queue_depth,
+            ..self.metrics
+        }
+    }
+}
@@ -0,0 +1,194 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::Window;
+use alloc::{boxed::Box, vec::Vec};
+use core::{convert::TryFrom, fmt};
+
+/// A windowing system event that this crate does not interpret itself,
+/// delivered as `EventType::Native`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NativeEvent {
+    /// An X11 `ClientMessage` of a type registered with
+    /// `Runtime::register_client_message`.
+    ClientMessage(ClientMessage),
+}
+
+/// An X11 `ClientMessage`, as used by protocols such as `_NET_WM_PING`
+/// and XDND.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ClientMessage {
+    /// The atom naming the message's type, as returned by
+    /// `Runtime::register_client_message`.
+    pub message_type: u32,
+    /// The message's payload.
+    pub data: ClientMessageData,
+}
+
+/// The 20-byte payload of a `ClientMessage`, in one of the three
+/// formats that X11 allows.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ClientMessageData {
+    /// Twenty 8-bit values.
+    Format8([u8; 20]),
+    /// Ten 16-bit values.
+    Format16([u16; 10]),
+    /// Five 32-bit values, which is what most protocols use.
+    Format32([u32; 5]),
+}
+
+impl ClientMessage {
+    /// Create a new `ClientMessage` with 32-bit data.
+    pub fn new(message_type: u32, data: [u32; 5]) -> Self {
+        ClientMessage {
+            message_type,
+            data: ClientMessageData::Format32(data),
+        }
+    }
+
+    /// Get the data of a 32-bit message, or `None` if it uses another
+    /// format.
+    pub fn data32(&self) -> Option<[u32; 5]> {
+        match self.data {
+            ClientMessageData::Format32(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Get the window named by the first 32-bit value, as many protocols
+    /// do, e.g. the source window in XDND messages.
+    pub fn window(&self) -> Option<Window> {
+        self.data32()
+            .and_then(|data| Window::try_from(data[0] as usize).ok())
+    }
+}
+
+/// An event exactly as the windowing system reported it, before the
+/// runtime translated it.
+///
+/// Pointers are stored as integers, as in `NativeHandle`. Only the
+/// variant for the platforms being compiled for exists.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RawEvent {
+    /// An X11 event, in its wire format: 32 bytes, or more for generic
+    /// events, as in `xcb_generic_event_t`.
+    #[cfg(unix)]
+    X11(Vec<u8>),
+    /// A Win32 message, as the fields of a `MSG`.
+    #[cfg(windows)]
+    Win32 {
+        /// The window the message was sent to.
+        hwnd: isize,
+        /// The message, e.g. `WM_POWERBROADCAST`.
+        message: u32,
+        /// The message's `WPARAM`.
+        wparam: usize,
+        /// The message's `LPARAM`.
+        lparam: isize,
+        /// When the message was posted.
+        time: u32,
+    },
+}
+
+/// What to do with a raw event after a `NativeEventFilter` has seen it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NativeFilterAction {
+    /// Pass the event on to the next filter, and then to the runtime.
+    Continue,
+    /// Drop the event, so that the runtime never sees it.
+    Consume,
+    /// Take the event away from the runtime, and deliver it to the
+    /// application as `EventType::PlatformSpecific` instead.
+    Deliver,
+}
+
+/// A function that sees each raw event before the runtime translates
+/// it, so that applications can handle messages this crate does not
+/// model.
+///
+/// Filters run on the GUI thread, in the order they were added, and
+/// should return quickly.
+pub type NativeEventFilter = Box<dyn FnMut(&RawEvent) -> NativeFilterAction + Send + 'static>;
+
+/// The ID of a filter added with `Runtime::add_native_event_filter`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NativeEventFilterId(u64);
+
+/// Runs native event filters in order.
+///
+/// This is intended for use by backends, which call `filter` on each
+/// raw event before translating it.
+#[derive(Default)]
+pub struct NativeEventFilters {
+    filters: Vec<(NativeEventFilterId, NativeEventFilter)>,
+    next_id: u64,
+}
+
+impl fmt::Debug for NativeEventFilters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeEventFilters")
+            .field(
+                "filters",
+                &self.filters.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl NativeEventFilters {
+    /// Create a new, empty `NativeEventFilters`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a filter, to run after the existing ones.
+    pub fn add(&mut self, filter: NativeEventFilter) -> NativeEventFilterId {
+        let id = NativeEventFilterId(self.next_id);
+        self.next_id += 1;
+        self.filters.push((id, filter));
+        id
+    }
+
+    /// Remove a filter, returning whether it was there.
+    pub fn remove(&mut self, id: NativeEventFilterId) -> bool {
+        let len = self.filters.len();
+        self.filters.retain(|(i, _)| *i != id);
+        self.filters.len() != len
+    }
+
+    /// Tell whether there are no filters.
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Remove every filter.
+    pub fn clear(&mut self) {
+        self.filters.clear();
+    }
+
+    /// Run the filters on a raw event, stopping at the first one that
+    /// does not return `NativeFilterAction::Continue`.
+    pub fn filter(&mut self, event: &RawEvent) -> NativeFilterAction {
+        self.filters
+            .iter_mut()
+            .map(|(_, filter)| filter(event))
+            .find(|action| *action != NativeFilterAction::Continue)
+            .unwrap_or(NativeFilterAction::Continue)
+    }
+}
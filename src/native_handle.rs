@@ -0,0 +1,134 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+//! Native handles to windows, for interoperating with other libraries.
+
+/// The native handles backing a window, as reported by
+/// `Runtime::window_native_handle`.
+///
+/// Pointers are stored as integers, since this crate does not use
+/// `unsafe` code. With the `raw-window-handle` feature enabled, they can
+/// be converted to `raw-window-handle`'s types in order to hand the window
+/// to libraries like `wgpu`, `glutin` or `softbuffer`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum NativeHandle {
+    /// A window on an X11 server, connected to through Xlib.
+    Xlib {
+        /// The X11 window ID.
+        window: u64,
+        /// The address of the Xlib `Display`.
+        display: usize,
+        /// The index of the screen the window is on.
+        screen: i32,
+    },
+    /// A window on an X11 server, connected to through XCB.
+    Xcb {
+        /// The X11 window ID.
+        window: u32,
+        /// The address of the `xcb_connection_t`.
+        connection: usize,
+        /// The index of the screen the window is on.
+        screen: i32,
+    },
+    /// A Win32 window.
+    Win32 {
+        /// The window's `HWND`.
+        hwnd: isize,
+        /// The `HINSTANCE` of the module that created the window.
+        hinstance: isize,
+    },
+    /// A Wayland surface.
+    Wayland {
+        /// The address of the `wl_surface`.
+        surface: usize,
+        /// The address of the `wl_display`.
+        display: usize,
+    },
+}
+
+#[cfg(feature = "raw-window-handle")]
+mod rwh {
+    use super::NativeHandle;
+    use raw_window_handle::{
+        RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle,
+        Win32WindowHandle, WindowsDisplayHandle, XcbDisplayHandle, XcbWindowHandle,
+        XlibDisplayHandle, XlibWindowHandle,
+    };
+
+    impl NativeHandle {
+        /// Convert this handle into `raw-window-handle`'s window handle.
+        ///
+        /// `HasRawWindowHandle` itself is an `unsafe` trait, so it cannot
+        /// be implemented here; wrap this value in a type of your own
+        /// that implements it instead.
+        pub fn raw_window_handle(&self) -> RawWindowHandle {
+            match *self {
+                NativeHandle::Xlib { window, .. } => {
+                    let mut handle = XlibWindowHandle::empty();
+                    handle.window = window as _;
+                    RawWindowHandle::Xlib(handle)
+                }
+                NativeHandle::Xcb { window, .. } => {
+                    let mut handle = XcbWindowHandle::empty();
+                    handle.window = window;
+                    RawWindowHandle::Xcb(handle)
+                }
+                NativeHandle::Win32 { hwnd, hinstance } => {
+                    let mut handle = Win32WindowHandle::empty();
+                    handle.hwnd = hwnd as _;
+                    handle.hinstance = hinstance as _;
+                    RawWindowHandle::Win32(handle)
+                }
+                NativeHandle::Wayland { surface, .. } => {
+                    let mut handle = WaylandWindowHandle::empty();
+                    handle.surface = surface as _;
+                    RawWindowHandle::Wayland(handle)
+                }
+            }
+        }
+
+        /// Convert this handle into `raw-window-handle`'s display handle.
+        pub fn raw_display_handle(&self) -> RawDisplayHandle {
+            match *self {
+                NativeHandle::Xlib {
+                    display, screen, ..
+                } => {
+                    let mut handle = XlibDisplayHandle::empty();
+                    handle.display = display as _;
+                    handle.screen = screen;
+                    RawDisplayHandle::Xlib(handle)
+                }
+                NativeHandle::Xcb {
+                    connection, screen, ..
+                } => {
+                    let mut handle = XcbDisplayHandle::empty();
+                    handle.connection = connection as _;
+                    handle.screen = screen;
+                    RawDisplayHandle::Xcb(handle)
+                }
+                NativeHandle::Win32 { .. } => {
+                    RawDisplayHandle::Windows(WindowsDisplayHandle::empty())
+                }
+                NativeHandle::Wayland { display, .. } => {
+                    let mut handle = WaylandDisplayHandle::empty();
+                    handle.display = display as _;
+                    RawDisplayHandle::Wayland(handle)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{Event, EventType, Rectangle, Window};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+/// A function that repaints part of a window.
+pub type PaintHandler = Box<dyn FnMut(Window, Rectangle) + Send + 'static>;
+
+/// Policy describing how the runtime paces painting.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PacingPolicy {
+    /// Service exposures directly with the window's paint handler,
+    /// instead of queueing an `EventType::Expose` event.
+    ///
+    /// This keeps up with expose storms, such as when another window
+    /// is dragged across this one, at the cost of painting outside of
+    /// the normal event order.
+    pub direct_expose: bool,
+}
+
+/// Counters describing how exposures were serviced.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PaintStats {
+    /// The number of exposures serviced directly by a paint handler.
+    pub direct_exposes: u64,
+    /// The number of exposures queued as events.
+    pub queued_exposes: u64,
+}
+
+/// Routes exposures either to a paint handler or to the event queue,
+/// according to the current `PacingPolicy`.
+///
+/// This is intended for use by backends.
+#[derive(Default)]
+pub struct ExposeDispatcher {
+    policy: PacingPolicy,
+    handlers: BTreeMap<Window, PaintHandler>,
+    stats: PaintStats,
+}
+
+impl fmt::Debug for ExposeDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExposeDispatcher")
+            .field("policy", &self.policy)
+            .field("handlers", &self.handlers.keys())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl ExposeDispatcher {
+    /// Create a new `ExposeDispatcher` with the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the pacing policy.
+    pub fn set_policy(&mut self, policy: PacingPolicy) {
+        self.policy = policy;
+    }
+
+    /// Set or remove the paint handler for a window.
+    pub fn set_handler(&mut self, window: Window, handler: Option<PaintHandler>) {
+        match handler {
+            Some(handler) => {
+                self.handlers.insert(window, handler);
+            }
+            None => {
+                self.handlers.remove(&window);
+            }
+        }
+    }
+
+    /// Service an exposure of `rect` in `window`.
+    ///
+    /// If the exposure was painted directly, this returns `None`.
+    /// Otherwise, it returns the event that should be queued.
+    pub fn dispatch(&mut self, window: Window, rect: Rectangle) -> Option<Event> {
+        if self.policy.direct_expose {
+            if let Some(handler) = self.handlers.get_mut(&window) {
+                handler(window, rect);
+                self.stats.direct_exposes += 1;
+                return None;
+            }
+        }
+
+        self.stats.queued_exposes += 1;
+        Some(Event::new(Some(window), EventType::Expose(rect)))
+    }
+
+    /// Get the counters describing how exposures were serviced.
+    pub fn stats(&self) -> PaintStats {
+        self.stats
+    }
+}
+
+/// Collects the rectangles invalidated in each window, so that they can
+/// be flushed to the windowing system at once.
+///
+/// This is intended for use by backends, which flush the batch right
+/// before waiting for the next event. Rectangles that touch are merged,
+/// so a handler that invalidates many small, adjacent areas causes a
+/// single `WM_PAINT` or `Expose`.
+#[derive(Debug, Clone, Default)]
+pub struct InvalidationBatch {
+    pending: BTreeMap<Window, Vec<Rectangle>>,
+}
+
+impl InvalidationBatch {
+    /// Create a new, empty `InvalidationBatch`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rectangle of `window` to the batch.
+    pub fn invalidate(&mut self, window: Window, mut rect: Rectangle) {
+        if rect.is_empty() {
+            return;
+        }
+
+        // merging can make the rectangle touch ones it did not before,
+        // so keep going until nothing else merges
+        let rects = self.pending.entry(window).or_default();
+        while let Some(i) = rects.iter().position(|r| r.touches(&rect)) {
+            rect = rect.union(&rects.swap_remove(i));
+        }
+        rects.push(rect);
+    }
+
+    /// Tell whether the batch is empty.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Forget the rectangles of a window, e.g. because it was destroyed.
+    pub fn remove(&mut self, window: Window) {
+        self.pending.remove(&window);
+    }
+
+    /// Clear the batch.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Take the rectangles of every window out of the batch.
+    pub fn drain(&mut self) -> impl Iterator<Item = (Window, Vec<Rectangle>)> {
+        core::mem::take(&mut self.pending).into_iter()
+    }
+}
@@ -0,0 +1,148 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::{Error, Image, Rectangle, Result};
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+use core::{fmt, num::NonZeroUsize};
+use std::{
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+/// A function that rasterizes one band of an image.
+///
+/// It is given the band's rectangle within the image and the band's
+/// RGBA pixels, in row-major order.
+pub type BandPainter = dyn Fn(Rectangle, &mut [u8]) + Send + Sync + 'static;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A small pool of threads that rasterize images in parallel.
+///
+/// Software rendering paths, like converting images for upload or
+/// filling gradients, split the image into horizontal bands and paint
+/// each band on a worker. The finished image is handed back to the
+/// calling thread, which should be the GUI thread, to be uploaded. See
+/// `Runtime::set_paint_workers`.
+pub struct PaintPool {
+    jobs: Option<mpsc::Sender<Job>>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl fmt::Debug for PaintPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaintPool")
+            .field("threads", &self.workers.len())
+            .finish()
+    }
+}
+
+impl PaintPool {
+    /// Create a new `PaintPool` with the given number of worker threads.
+    pub fn new(threads: NonZeroUsize) -> Result<Self> {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.get())
+            .map(|index| {
+                let receiver = receiver.clone();
+                thread::Builder::new()
+                    .name(alloc::format!("gui-tools paint worker {}", index))
+                    .spawn(move || loop {
+                        // hold the lock only while taking a job
+                        let job = match receiver.lock() {
+                            Ok(receiver) => receiver.recv(),
+                            Err(_) => break,
+                        };
+
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break,
+                        }
+                    })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        Ok(PaintPool {
+            jobs: Some(jobs),
+            workers,
+        })
+    }
+
+    /// Get the number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Paint an image of the given size, one band per worker, and wait for
+    /// every band to finish.
+    pub fn rasterize(&self, width: u32, height: u32, painter: Arc<BandPainter>) -> Result<Image> {
+        let stride = width as usize * 4;
+        let band_height = match height as usize {
+            0 => 0,
+            height => height.div_ceil(self.threads()),
+        };
+
+        let (sender, results) = mpsc::channel();
+        let mut bands = 0;
+        let mut y = 0;
+        while y < height {
+            let rows = (band_height as u32).min(height - y);
+            let rect = Rectangle::new(0, y as i32, width, rows);
+            let sender = sender.clone();
+            let painter = painter.clone();
+            self.submit(Box::new(move || {
+                let mut pixels = vec![0; stride * rows as usize];
+                painter(rect, &mut pixels);
+                let _ = sender.send((rect.y as usize, pixels));
+            }))?;
+
+            bands += 1;
+            y += rows;
+        }
+        drop(sender);
+
+        let mut pixels = vec![0; stride * height as usize];
+        for _ in 0..bands {
+            // if a worker panicked, its sender is dropped without a result
+            let (y, band) = results
+                .recv()
+                .map_err(|_| Error::static_msg("A paint worker panicked"))?;
+            let start = y * stride;
+            pixels[start..start + band.len()].copy_from_slice(&band);
+        }
+
+        Image::new(width, height, pixels)
+            .ok_or_else(|| Error::static_msg("Image is too large to rasterize"))
+    }
+
+    fn submit(&self, job: Job) -> Result {
+        self.jobs
+            .as_ref()
+            .and_then(|jobs| jobs.send(job).ok())
+            .ok_or_else(|| Error::static_msg("The paint pool has shut down"))
+    }
+}
+
+impl Drop for PaintPool {
+    fn drop(&mut self) {
+        // closing the channel stops the workers once they are idle
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::Window;
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::fmt;
+use spin::RwLock;
+
+/// The number of shards in a `WindowRegistry`.
+const SHARDS: usize = 16;
+
+/// A thread-safe map from windows to per-window state, for use by
+/// backends.
+///
+/// The map is split into shards, each behind its own lock, so that
+/// lookups for different windows rarely contend. Values are handed out
+/// as `Arc`s, so no lock is held while the caller uses them.
+pub struct WindowRegistry<T> {
+    shards: [RwLock<BTreeMap<Window, Arc<T>>>; SHARDS],
+}
+
+impl<T> Default for WindowRegistry<T> {
+    fn default() -> Self {
+        WindowRegistry {
+            shards: Default::default(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for WindowRegistry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowRegistry")
+            .field("windows", &self.windows())
+            .finish()
+    }
+}
+
+impl<T> WindowRegistry<T> {
+    /// Create a new, empty `WindowRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn shard(&self, window: Window) -> &RwLock<BTreeMap<Window, Arc<T>>> {
+        &self.shards[window.id().get() % SHARDS]
+    }
+
+    /// Insert the state for a window, returning the previous state if
+    /// there was any.
+    pub fn insert(&self, window: Window, value: T) -> Option<Arc<T>> {
+        self.shard(window).write().insert(window, Arc::new(value))
+    }
+
+    /// Get the state for a window.
+    pub fn get(&self, window: Window) -> Option<Arc<T>> {
+        self.shard(window).read().get(&window).cloned()
+    }
+
+    /// Remove the state for a window, returning it if there was any.
+    pub fn remove(&self, window: Window) -> Option<Arc<T>> {
+        self.shard(window).write().remove(&window)
+    }
+
+    /// Tell whether the registry contains state for a window.
+    pub fn contains(&self, window: Window) -> bool {
+        self.shard(window).read().contains_key(&window)
+    }
+
+    /// Get the number of windows in the registry.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().len()).sum()
+    }
+
+    /// Tell whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.read().is_empty())
+    }
+
+    /// Get every window in the registry.
+    ///
+    /// Since shards are locked one at a time, this is not an atomic
+    /// snapshot if other threads are modifying the registry.
+    pub fn windows(&self) -> Vec<Window> {
+        let mut windows: Vec<Window> = self
+            .shards
+            .iter()
+            .flat_map(|shard| shard.read().keys().copied().collect::<Vec<_>>())
+            .collect();
+        windows.sort_unstable();
+        windows
+    }
+
+    /// Remove every window from the registry.
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            shard.write().clear();
+        }
+    }
+}
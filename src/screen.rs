@@ -0,0 +1,53 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{Rectangle, BASE_DPI};
+
+/// Information about a screen, or monitor, attached to the display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ScreenInfo {
+    /// The area the screen covers on the virtual desktop.
+    pub geometry: Rectangle,
+    /// Whether or not this is the primary screen.
+    pub primary: bool,
+    /// The screen's DPI, where `BASE_DPI` is unscaled.
+    pub dpi: u32,
+}
+
+impl ScreenInfo {
+    /// Create a new, unscaled `ScreenInfo`.
+    pub fn new(geometry: Rectangle, primary: bool) -> Self {
+        ScreenInfo {
+            geometry,
+            primary,
+            dpi: BASE_DPI,
+        }
+    }
+
+    /// Use the given DPI.
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Get the number of physical pixels per logical pixel on this
+    /// screen.
+    pub fn scale_factor(&self) -> f64 {
+        f64::from(self.dpi) / f64::from(BASE_DPI)
+    }
+}
@@ -0,0 +1,98 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{Error, KeyInfo, Result};
+use alloc::collections::BTreeMap;
+
+/// The identifier for a registered keyboard shortcut.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ShortcutId(u32);
+
+impl ShortcutId {
+    /// Create a new shortcut identifier.
+    pub fn new(id: u32) -> Self {
+        ShortcutId(id)
+    }
+
+    /// Get the inner identifier.
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+/// Where a keyboard shortcut is active.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShortcutScope {
+    /// The shortcut is active while any window of this application has
+    /// the keyboard focus.
+    Application,
+    /// The shortcut is active system-wide, even when other applications
+    /// have the keyboard focus.
+    ///
+    /// This uses `XGrabKey` on the root window on X11 and
+    /// `RegisterHotKey` on Windows.
+    Global,
+}
+
+/// The set of keyboard shortcuts registered with a runtime.
+///
+/// This is intended for use by backends.
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutMap {
+    shortcuts: BTreeMap<KeyInfo, (ShortcutId, ShortcutScope)>,
+    next_id: u32,
+}
+
+impl ShortcutMap {
+    /// Create a new, empty `ShortcutMap`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a shortcut, returning its new identifier.
+    ///
+    /// Returns an error of kind `ErrorKind::Conflict` if the key
+    /// combination is already registered.
+    pub fn register(&mut self, key: KeyInfo, scope: ShortcutScope) -> Result<ShortcutId> {
+        if self.shortcuts.contains_key(&key) {
+            return Err(Error::conflict("Keyboard shortcut"));
+        }
+
+        let id = ShortcutId::new(self.next_id);
+        self.next_id += 1;
+        self.shortcuts.insert(key, (id, scope));
+        Ok(id)
+    }
+
+    /// Unregister a shortcut, returning the key combination and scope it
+    /// was registered with.
+    pub fn unregister(&mut self, id: ShortcutId) -> Option<(KeyInfo, ShortcutScope)> {
+        let key = self
+            .shortcuts
+            .iter()
+            .find(|(_, &(other, _))| other == id)
+            .map(|(&key, _)| key)?;
+        let (_, scope) = self.shortcuts.remove(&key)?;
+        Some((key, scope))
+    }
+
+    /// Find the shortcut that the given key combination triggers.
+    pub fn find(&self, key: &KeyInfo) -> Option<(ShortcutId, ShortcutScope)> {
+        self.shortcuts.get(key).copied()
+    }
+}
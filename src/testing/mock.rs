@@ -0,0 +1,217 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{
+    accessibility::{AccessNode, Priority},
+    async_runtime::Task,
+    AttentionType, ChildLayout, ClientMessage, ClipboardContent, ClipboardFormat, ControlKind,
+    Cursor, Error, Event, EventMask, HitTestHandler, HoverConfig, IdleCallback, IdleId, KeyInfo,
+    Menu, NativeEventFilter, NativeEventFilterId, NativeHandle, PacingPolicy, PaintHandler,
+    QueuePolicy, ReconnectPolicy, Rectangle, ResizeEdge, Result, Selection, ShortcutId,
+    ShortcutScope, Theme, UserData, VideoFrame, Visibility, Window, WindowConfig, WindowProps,
+};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, VecDeque},
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    any::{Any, TypeId},
+    time::Duration,
+};
+
+/// A `Runtime` that does nothing but record the calls made to it and
+/// return the responses it has been programmed with.
+///
+/// Unlike `TestRuntime`, no state is kept: each call returns the next
+/// response queued for that method with `respond`. If no response is
+/// queued, methods that return nothing succeed, and other methods fail
+/// with an unsupported error.
+///
+/// ```ignore
+/// let mut mock = MockRuntime::new();
+/// mock.respond("create_window", Ok(window));
+/// mock.push_event(Event::new(Some(window), EventType::Resized { width: 10, height: 10 }));
+///
+/// my_toolkit::run(&mut mock).await?;
+/// assert!(mock.called("window_set_geometry"));
+/// ```
+#[derive(Debug, Default)]
+pub struct MockRuntime {
+    calls: Vec<Call>,
+    responses: BTreeMap<&'static str, VecDeque<Response>>,
+}
+
+#[derive(Debug)]
+struct Response(Box<dyn Any + Send>);
+
+/// A call made to a `MockRuntime`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Call {
+    /// The name of the `Runtime` method that was called.
+    pub method: &'static str,
+    /// The arguments the method was called with, formatted with their
+    /// `Debug` implementations.
+    pub args: Vec<String>,
+}
+
+impl MockRuntime {
+    /// Create a new `MockRuntime` with no programmed responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response for the next call to `method`.
+    ///
+    /// # Panics
+    ///
+    /// The call to `method` panics if `T` is not the type that the
+    /// method returns.
+    pub fn respond<T: Send + 'static>(&mut self, method: &'static str, response: Result<T>) {
+        self.responses
+            .entry(method)
+            .or_default()
+            .push_back(Response(Box::new(response)));
+    }
+
+    /// Queue an event to be returned by `next_event`.
+    pub fn push_event(&mut self, event: Event) {
+        self.respond("next_event", Ok(event));
+    }
+
+    /// Get the calls made to this runtime, in order.
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Take the calls made to this runtime, leaving the list empty.
+    pub fn take_calls(&mut self) -> Vec<Call> {
+        core::mem::take(&mut self.calls)
+    }
+
+    /// Tell whether `method` has been called.
+    pub fn called(&self, method: &str) -> bool {
+        self.calls.iter().any(|call| call.method == method)
+    }
+
+    /// Get the number of times that `method` has been called.
+    pub fn call_count(&self, method: &str) -> usize {
+        self.calls
+            .iter()
+            .filter(|call| call.method == method)
+            .count()
+    }
+
+    /// Record a call, and produce its response.
+    pub(crate) fn intercept<T: 'static>(
+        &mut self,
+        method: &'static str,
+        args: Vec<String>,
+    ) -> Result<T> {
+        self.calls.push(Call { method, args });
+
+        let response = self
+            .responses
+            .get_mut(method)
+            .and_then(|responses| responses.pop_front());
+
+        match response {
+            Some(Response(response)) => match response.downcast::<Result<T>>() {
+                Ok(response) => *response,
+                Err(_) => panic!("mock response for `{}` has the wrong type", method),
+            },
+            None if TypeId::of::<T>() == TypeId::of::<()>() => {
+                let unit: Box<dyn Any> = Box::new(());
+                Ok(*unit.downcast::<T>().unwrap())
+            }
+            None => Err(Error::unsupported(method)),
+        }
+    }
+}
+
+/// An argument to a `Runtime` method, which can be recorded by a
+/// `MockRuntime`.
+pub(crate) trait CallArg {
+    /// Format this argument for the call log.
+    fn describe(&self) -> String;
+}
+
+macro_rules! debug_call_args {
+    ($($ty: ty),*) => {
+        $(
+            impl CallArg for $ty {
+                fn describe(&self) -> String {
+                    format!("{:?}", self)
+                }
+            }
+        )*
+    }
+}
+
+debug_call_args! {
+    bool, i32, usize, f32, &str, Duration, &[Rectangle], AttentionType, ChildLayout, ClientMessage, ClipboardFormat, ControlKind, Cursor, Event, EventMask, HoverConfig, IdleId, KeyInfo, Menu,
+    NativeEventFilterId, NativeHandle, Option<AccessNode>, Option<ChildLayout>, Option<Menu>, Option<Rectangle>, Option<Theme>, Priority, PacingPolicy, QueuePolicy, Rectangle, ReconnectPolicy, ResizeEdge,
+    Selection, ShortcutId, ShortcutScope, Vec<ClipboardContent>, &VideoFrame, Visibility, Window,
+    WindowConfig, WindowProps
+}
+
+impl CallArg for Option<UserData> {
+    fn describe(&self) -> String {
+        match self {
+            Some(_) => "Some(<user data>)".into(),
+            None => "None".into(),
+        }
+    }
+}
+
+impl CallArg for Task {
+    fn describe(&self) -> String {
+        "<task>".into()
+    }
+}
+
+impl CallArg for IdleCallback {
+    fn describe(&self) -> String {
+        "<idle callback>".into()
+    }
+}
+
+impl CallArg for Option<HitTestHandler> {
+    fn describe(&self) -> String {
+        match self {
+            Some(_) => "Some(<hit test handler>)".into(),
+            None => "None".into(),
+        }
+    }
+}
+
+impl CallArg for NativeEventFilter {
+    fn describe(&self) -> String {
+        "<native event filter>".into()
+    }
+}
+
+impl CallArg for Option<PaintHandler> {
+    fn describe(&self) -> String {
+        match self {
+            Some(_) => "Some(<paint handler>)".into(),
+            None => "None".into(),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! Utilities for testing code built on top of `gui-tools`.
+
+mod mock;
+pub(crate) use mock::CallArg;
+pub use mock::{Call, MockRuntime};
+
+mod runtime;
+pub use runtime::{LogEntry, TestRuntime};
+
+mod script;
+pub use script::Script;
+
+/// Resolve a future that is ready immediately, as the `TestRuntime`'s
+/// are.
+#[cfg(test)]
+pub(crate) fn now<F: core::future::Future>(future: F) -> F::Output {
+    use core::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future was not ready"),
+    }
+}
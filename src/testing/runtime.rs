@@ -0,0 +1,1622 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{
+    accessibility::{AccessNode, Priority},
+    async_runtime::{ready, Executor, Runtime, RuntimeFuture, ShutdownReport, Task},
+    recreation_order, AttentionType, AutoLayout, Capabilities, CaretBlinker, ChildLayout,
+    ClickTracker, ClientMessage, ClipboardContent, ClipboardFormat, ControlCommand, ControlKind,
+    CrossingTracker, Cursor, Error, Event, EventDelivery, EventMask, EventQueue, EventType,
+    ExposeDispatcher, GcKey, GcPool, HitTestHandler, HitTestMap, HitTestResult, HoverConfig,
+    HoverTracker, IdleCallback, IdleId, IdleQueue, ImageCache, InvalidationBatch, KeyInfo, Menu,
+    Metrics, MetricsRecorder, MouseButton, NativeEvent, NativeEventFilter, NativeEventFilterId,
+    NativeEventFilters, NativeFilterAction, NativeHandle, PacingPolicy, PaintHandler, PaintStats,
+    QueuePolicy, RawEvent, ReconnectPolicy, Rectangle, ResizeEdge, ScreenInfo, Selection,
+    ShortcutId, ShortcutMap, ShortcutScope, Theme, UserData, VideoFrame, Visibility,
+    VisualPreference, Window, WindowConfig, WindowInfo, WindowProps, WindowState,
+    DEFAULT_IDLE_BUDGET,
+};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::{num::NonZeroUsize, time::Duration};
+
+/// A headless `Runtime` that records everything done to it.
+///
+/// No windowing system is involved: windows only exist as records, and
+/// events only come from `inject_event`. The calls made to the runtime
+/// are kept in a log, which can be inspected to test code built on top
+/// of `gui-tools`.
+#[derive(Debug)]
+pub struct TestRuntime {
+    windows: BTreeMap<Window, WindowProps>,
+    /// Windows, from the bottom of the stack to the top.
+    stacking: Vec<Window>,
+    next_window: NonZeroUsize,
+    events: Box<dyn EventDelivery>,
+    exposes: ExposeDispatcher,
+    clicks: ClickTracker,
+    hovers: HoverTracker,
+    idle: IdleQueue,
+    tasks: Executor,
+    crossings: CrossingTracker,
+    invalidations: InvalidationBatch,
+    metrics: MetricsRecorder,
+    carets: BTreeMap<Window, CaretBlinker>,
+    user_data: BTreeMap<Window, UserData>,
+    /// The kinds of the windows that are native controls.
+    controls: BTreeMap<Window, ControlKind>,
+    /// Title bar themes of windows that do not follow the system.
+    titlebar_themes: BTreeMap<Window, Theme>,
+    hit_tests: HitTestMap,
+    /// The images of custom cursors, uploaded to the simulated server
+    /// and identified there by their image ID.
+    images: ImageCache<usize>,
+    /// The graphics contexts that windows present with, identified on
+    /// the simulated server by their key.
+    gcs: GcPool<GcKey>,
+    native_filters: NativeEventFilters,
+    layout: AutoLayout,
+    /// Windows with a `RedrawRequested` event queued.
+    redraws: BTreeSet<Window>,
+    /// The simulated monotonic clock, advanced by `advance_time`.
+    clock: Duration,
+    /// Whether the simulated display connection is still up.
+    connected: bool,
+    reconnect: ReconnectPolicy,
+    /// The application ID given to windows without their own.
+    app_id: Option<String>,
+    /// The atoms of the client message types registered so far.
+    client_messages: BTreeMap<String, u32>,
+    capabilities: Capabilities,
+    screens: Vec<ScreenInfo>,
+    shortcuts: ShortcutMap,
+    theme: Theme,
+    selections: BTreeMap<Selection, Vec<ClipboardContent>>,
+    /// Configuration changes buffered until the next flush.
+    pending: BTreeMap<Window, WindowConfig>,
+    log: Vec<LogEntry>,
+}
+
+/// An entry in the log of a `TestRuntime`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum LogEntry {
+    /// A keyboard shortcut was registered.
+    RegisterShortcut(ShortcutId, KeyInfo, ShortcutScope),
+    /// A keyboard shortcut was unregistered.
+    UnregisterShortcut(ShortcutId),
+    /// A window was created.
+    CreateWindow(Window, WindowProps),
+    /// A native control was created.
+    CreateControl(Window, ControlKind),
+    /// A window was destroyed.
+    DestroyWindow(Window),
+    /// An event was injected.
+    InjectEvent(Event),
+    /// A window's geometry was set.
+    SetGeometry(Window, Rectangle),
+    /// Automatic layout was enabled or disabled.
+    SetAutoLayout(bool),
+    /// A layout was attached to or detached from a window.
+    SetLayout(Window, Option<ChildLayout>),
+    /// A redraw of a window was requested.
+    RequestRedraw(Window),
+    /// Part of a window was invalidated.
+    Invalidate(Window, Rectangle),
+    /// A window's back buffer was presented with the given damage.
+    Present(Window, Vec<Rectangle>),
+    /// A frame of video was presented in a window.
+    PresentFrame(Window, VideoFrame, Rectangle),
+    /// A window's title was set.
+    SetTitle(Window, String),
+    /// A batch of changes to a window was buffered.
+    ConfigureBatch(Window, WindowConfig),
+    /// Data was placed in a selection.
+    ClipboardWrite(Selection, Vec<ClipboardContent>),
+    /// Buffered requests were flushed.
+    Flush,
+    /// A window was moved into a new parent.
+    Reparent(Window, Window, i32, i32),
+    /// A window was brought to the front and focused.
+    Activate(Window),
+    /// An interactive move of a window was started.
+    BeginMove(Window),
+    /// An interactive resize of a window was started.
+    BeginResize(Window, ResizeEdge),
+    /// A window was moved in the stacking order.
+    Restack(Window),
+    /// A window's visibility was set.
+    SetVisibility(Window, Visibility),
+    /// A window's caret was placed or removed.
+    SetCaret(Window, Option<Rectangle>),
+    /// A window's title bar theme was set, or set to follow the system.
+    SetTitlebarTheme(Window, Option<Theme>),
+    /// A window's opacity was set.
+    SetAlpha(Window, f32),
+    /// A window asked for the user's attention.
+    RequestAttention(Window, AttentionType),
+    /// Whether a window is left out of the taskbar was set.
+    SetSkipTaskbar(Window, bool),
+    /// A window's user data was set or removed.
+    SetUserData(Window, bool),
+    /// A foreign window was embedded in a window.
+    EmbedForeign(Window, NativeHandle, Rectangle),
+    /// A window's event mask was set.
+    SetEventMask(Window, EventMask),
+    /// A window's hover and long-press delays were set.
+    SetHoverConfig(Window, HoverConfig),
+    /// A window's hit test handler was set or removed.
+    SetHitTest(Window, bool),
+    /// A window's cursor was set.
+    SetCursor(Window, Cursor),
+    /// A window's menu bar was set or removed.
+    SetMenuBar(Window, Option<Menu>),
+    /// A window's accessibility tree was set or removed.
+    SetAccessTree(Window, Option<AccessNode>),
+    /// Text was announced to screen readers.
+    Announce(String, Priority),
+    /// A context menu was displayed.
+    PopupContextMenu(Window, Menu, i32, i32),
+    /// A window's paint handler was set or removed.
+    SetPaintHandler(Window, bool),
+    /// An idle callback was registered.
+    OnIdle(IdleId),
+    /// An idle callback was removed.
+    CancelIdle(IdleId),
+    /// A native event filter was added.
+    AddNativeEventFilter(NativeEventFilterId),
+    /// A native event filter was removed.
+    RemoveNativeEventFilter(NativeEventFilterId),
+    /// The double-click interval was set.
+    SetDoubleClickInterval(Duration),
+    /// The event queue policy was set.
+    SetQueuePolicy(QueuePolicy),
+    /// The reconnect policy was set.
+    SetReconnectPolicy(ReconnectPolicy),
+    /// A client message type was registered, and given this atom.
+    RegisterClientMessage(String, u32),
+    /// A client message was sent to a window.
+    SendClientMessage(Window, ClientMessage),
+    /// The runtime reconnected to the display, and recreated these
+    /// windows in order.
+    Reconnect(Vec<Window>),
+    /// The image cache budget was set.
+    SetImageCacheBudget(usize),
+    /// The image cache was purged.
+    PurgeImageCache,
+    /// An image, identified by its ID, was uploaded to the server.
+    UploadImage(usize),
+    /// An uploaded image, identified by its ID, was evicted from the
+    /// image cache and freed on the server.
+    FreeImage(usize),
+    /// A graphics context was created for windows with this key.
+    CreateGc(GcKey),
+    /// The graphics context for windows with this key was freed, since
+    /// no window uses it any more.
+    FreeGc(GcKey),
+    /// The number of paint worker threads was set.
+    SetPaintWorkers(usize),
+    /// The pacing policy was set.
+    SetPacingPolicy(PacingPolicy),
+    /// The application ID was set.
+    SetAppId(String),
+    /// The application registered with the session manager.
+    SetSessionId(String),
+    /// The application responded to the session ending.
+    SessionEndResponse(bool),
+    /// The runtime was shut down.
+    Shutdown(ShutdownReport),
+}
+
+impl Default for TestRuntime {
+    fn default() -> Self {
+        TestRuntime {
+            windows: BTreeMap::new(),
+            stacking: Vec::new(),
+            next_window: NonZeroUsize::new(1).unwrap(),
+            events: Box::new(EventQueue::new()),
+            exposes: ExposeDispatcher::new(),
+            clicks: ClickTracker::default(),
+            hovers: HoverTracker::new(),
+            idle: IdleQueue::new(),
+            tasks: Executor::new(),
+            crossings: CrossingTracker::new(),
+            invalidations: InvalidationBatch::new(),
+            metrics: MetricsRecorder::new(),
+            carets: BTreeMap::new(),
+            user_data: BTreeMap::new(),
+            titlebar_themes: BTreeMap::new(),
+            controls: BTreeMap::new(),
+            hit_tests: HitTestMap::new(),
+            images: ImageCache::default(),
+            gcs: GcPool::new(),
+            native_filters: NativeEventFilters::new(),
+            layout: AutoLayout::new(),
+            capabilities: Capabilities {
+                primary_selection: true,
+                clipboard_images: true,
+                native_controls: true,
+                ..Capabilities::default()
+            },
+            selections: BTreeMap::new(),
+            theme: Theme::default(),
+            shortcuts: ShortcutMap::new(),
+            redraws: BTreeSet::new(),
+            clock: Duration::from_secs(0),
+            connected: true,
+            reconnect: ReconnectPolicy::default(),
+            app_id: None,
+            client_messages: BTreeMap::new(),
+            screens: alloc::vec![ScreenInfo::new(Rectangle::new(0, 0, 1920, 1080), true)],
+            pending: BTreeMap::new(),
+            log: Vec::new(),
+        }
+    }
+}
+
+impl TestRuntime {
+    /// Create a new, empty `TestRuntime`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty `TestRuntime` that holds its events in the
+    /// given delivery, rather than an `EventQueue`.
+    ///
+    /// A producer registered with `add_backend` can pass
+    /// `RuntimeBuilder::event_delivery` here.
+    pub fn with_delivery<D: EventDelivery + 'static>(delivery: D) -> Self {
+        TestRuntime {
+            events: Box::new(delivery),
+            ..Self::default()
+        }
+    }
+
+    /// Get the log of calls made to this runtime.
+    pub fn log(&self) -> &[LogEntry] {
+        &self.log
+    }
+
+    /// Take the log of calls made to this runtime, leaving it empty.
+    pub fn take_log(&mut self) -> Vec<LogEntry> {
+        core::mem::take(&mut self.log)
+    }
+
+    /// Get the properties of a window created in this runtime.
+    pub fn window_props(&self, window: Window) -> Option<&WindowProps> {
+        self.windows.get(&window)
+    }
+
+    /// Get the stacking order of the windows in this runtime, from
+    /// bottom to top.
+    pub fn stacking_order(&self) -> &[Window] {
+        &self.stacking
+    }
+
+    /// Set the capabilities the runtime reports, to test how code
+    /// copes with features being unavailable.
+    ///
+    /// Unlike `set_compositing`, this queues no events.
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        self.capabilities = capabilities;
+    }
+
+    /// Simulate a compositor starting or stopping.
+    ///
+    /// This updates the runtime's capabilities and queues an
+    /// `EventType::CompositorChanged` event.
+    pub fn set_compositing(&mut self, compositing: bool) {
+        self.capabilities.compositing = compositing;
+        self.capabilities.transparency = compositing;
+        self.events
+            .push(Event::new(None, EventType::CompositorChanged(compositing)));
+    }
+
+    /// Simulate screens being added, removed or rearranged.
+    ///
+    /// This queues an `EventType::ScreenConfigurationChanged` event.
+    pub fn set_screens(&mut self, screens: Vec<ScreenInfo>) {
+        self.screens = screens;
+        self.events
+            .push(Event::new(None, EventType::ScreenConfigurationChanged));
+    }
+
+    /// Simulate the user changing their preferred color scheme.
+    ///
+    /// This queues an `EventType::SystemThemeChanged` event.
+    pub fn set_system_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.events
+            .push(Event::new(None, EventType::SystemThemeChanged(theme)));
+    }
+
+    /// Simulate the user switching keyboard layouts.
+    ///
+    /// This queues an `EventType::KeymapChanged` event.
+    pub fn change_keymap(&mut self) {
+        self.events.push(Event::new(None, EventType::KeymapChanged));
+    }
+
+    /// Simulate the system being suspended or resumed.
+    ///
+    /// This queues an `EventType::PowerSuspend` or
+    /// `EventType::PowerResume` event.
+    pub fn set_suspended(&mut self, suspended: bool) {
+        let ty = if suspended {
+            EventType::PowerSuspend
+        } else {
+            EventType::PowerResume
+        };
+        self.events.push(Event::new(None, ty));
+    }
+
+    /// Simulate the connection to the display server being lost.
+    ///
+    /// This queues an `EventType::DisplayDisconnected` event. If the
+    /// reconnect policy is `ReconnectPolicy::Recreate`, the runtime
+    /// reconnects at once, recreating its windows, queueing an
+    /// `EventType::DisplayReconnected` event and exposing every visible
+    /// window. Otherwise, every later call fails.
+    pub fn disconnect(&mut self) {
+        self.events
+            .push(Event::new(None, EventType::DisplayDisconnected));
+
+        match self.reconnect {
+            ReconnectPolicy::Never => self.connected = false,
+            ReconnectPolicy::Recreate { .. } => {
+                let order = recreation_order(self.windows.iter().map(|(&w, props)| (w, props)));
+                self.log.push(LogEntry::Reconnect(order.clone()));
+                self.events
+                    .push(Event::new(None, EventType::DisplayReconnected));
+
+                for window in order {
+                    let props = &self.windows[&window];
+                    if props.visibility != Visibility::Visible {
+                        continue;
+                    }
+
+                    let rect = Rectangle::new(0, 0, props.geometry.width, props.geometry.height);
+                    if let Some(event) = self.exposes.dispatch(window, rect) {
+                        self.events.push(event);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Simulate the user clicking a button control.
+    ///
+    /// This queues an `EventType::ControlCommand` event.
+    pub fn click_control(&mut self, window: Window) {
+        if self.controls.get(&window) == Some(&ControlKind::Button) {
+            self.events.push(Event::new(
+                Some(window),
+                EventType::ControlCommand(ControlCommand::Clicked),
+            ));
+        }
+    }
+
+    /// Simulate the user replacing the text of an edit control.
+    ///
+    /// This queues an `EventType::ControlCommand` event.
+    pub fn edit_control(&mut self, window: Window, text: &str) {
+        if self.controls.get(&window) != Some(&ControlKind::Edit) {
+            return;
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.title = text.into();
+            self.events.push(Event::new(
+                Some(window),
+                EventType::ControlCommand(ControlCommand::Changed),
+            ));
+        }
+    }
+
+    /// Simulate another client sending a `ClientMessage` to a window.
+    ///
+    /// This queues an `EventType::Native` event if the message's type
+    /// was registered with `Runtime::register_client_message`, and
+    /// discards the message otherwise.
+    pub fn receive_client_message(&mut self, window: Window, message: ClientMessage) {
+        let registered = self
+            .client_messages
+            .values()
+            .any(|&atom| atom == message.message_type);
+        if registered && self.windows.contains_key(&window) {
+            self.events.push(Event::new(
+                Some(window),
+                EventType::Native(NativeEvent::ClientMessage(message)),
+            ));
+        }
+    }
+
+    /// Simulate the windowing system reporting a raw event, which is run
+    /// through the native event filters.
+    ///
+    /// This queues an `EventType::PlatformSpecific` event if a filter
+    /// asks for it, and returns what the filters decided.
+    pub fn receive_raw_event(
+        &mut self,
+        window: Option<Window>,
+        event: RawEvent,
+    ) -> NativeFilterAction {
+        let action = self.native_filters.filter(&event);
+        if action == NativeFilterAction::Deliver {
+            self.events
+                .push(Event::new(window, EventType::PlatformSpecific(event)));
+        }
+        action
+    }
+
+    /// Simulate the session manager ending the session.
+    ///
+    /// This queues an `EventType::SessionEnding` event.
+    pub fn end_session(&mut self, can_veto: bool) {
+        self.events
+            .push(Event::new(None, EventType::SessionEnding { can_veto }));
+    }
+
+    /// Simulate the pointer moving over a window, or out of every window
+    /// if `window` is `None`.
+    ///
+    /// This queues the `MouseEntered` and `MouseLeft` events a backend
+    /// would deliver.
+    pub fn move_pointer(&mut self, window: Option<Window>) {
+        let mut path = Vec::new();
+        let mut next = window.filter(|w| self.windows.contains_key(w));
+        while let Some(window) = next {
+            path.push(window);
+            next = self.windows.get(&window).and_then(|props| props.parent);
+        }
+        path.reverse();
+
+        for event in self.crossings.update(&path) {
+            let event = event.with_timestamp(self.clock);
+            self.hovers.track(&event);
+            self.events.push(event);
+        }
+    }
+
+    /// Simulate time passing, blinking any carets.
+    ///
+    /// This queues any hovers and long presses that became due, and an
+    /// exposure of each caret that toggled.
+    pub fn advance_time(&mut self, elapsed: Duration) {
+        self.clock += elapsed;
+        self.fire_hovers(self.clock);
+
+        for (&window, caret) in &mut self.carets {
+            if let Some(rect) = caret.advance(elapsed) {
+                if let Some(event) = self.exposes.dispatch(window, rect) {
+                    self.events.push(event);
+                }
+            }
+        }
+    }
+
+    /// Get the current time on the simulated clock used to timestamp
+    /// events.
+    ///
+    /// This starts at zero, and moves with `advance_time` and with the
+    /// timestamps of injected events.
+    pub fn now(&self) -> Duration {
+        self.clock
+    }
+
+    /// Queue the hovers and long presses that became due by `now`, each
+    /// at its deadline, as a backend's timer would.
+    fn fire_hovers(&mut self, now: Duration) {
+        while let Some(deadline) = self.hovers.next_deadline().filter(|&d| d <= now) {
+            for event in self.hovers.advance(deadline) {
+                self.events.push(event);
+            }
+        }
+    }
+
+    /// Get the theme a window's title bar is drawn with, or `None` if
+    /// the window does not exist.
+    ///
+    /// Windows without a theme of their own follow the system theme.
+    pub fn titlebar_theme(&self, window: Window) -> Option<Theme> {
+        if self.windows.contains_key(&window) {
+            Some(
+                self.titlebar_themes
+                    .get(&window)
+                    .copied()
+                    .unwrap_or(self.theme),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of events waiting to be received.
+    pub fn pending_events(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Take the next event that its window's mask selects, or `None`
+    /// if there is no such event.
+    pub(crate) fn take_event(&mut self) -> Option<Event> {
+        // flush the invalidations made while handling the last event
+        for (window, rects) in self.invalidations.drain() {
+            for rect in rects {
+                if let Some(event) = self.exposes.dispatch(window, rect) {
+                    self.events.push(event);
+                }
+            }
+        }
+
+        // tasks whose wakeups arrived while handling the last event
+        self.tasks.run_ready();
+
+        // this is where a backend would block, so do idle work first
+        if self.events.is_empty() {
+            self.idle.run(DEFAULT_IDLE_BUDGET);
+        }
+
+        let event = loop {
+            let event = match self.events.pop() {
+                Some(event) => event,
+                None => break None,
+            };
+
+            if let (Some(window), EventType::RedrawRequested) = (event.window(), event.ty()) {
+                self.redraws.remove(&window);
+            }
+
+            // windows only receive the events their mask selects
+            let selected = match event.window().and_then(|w| self.windows.get(&w)) {
+                Some(props) => props.event_mask.allows(event.ty()),
+                None => true,
+            };
+            if selected {
+                break Some(event);
+            }
+        };
+
+        if let Some(ref event) = event {
+            self.metrics.record_event();
+            if let (Some(window), EventType::Expose(_))
+            | (Some(window), EventType::RedrawRequested) = (event.window(), event.ty())
+            {
+                self.metrics.begin_paint(window, self.clock);
+            }
+        }
+
+        // events the runtime produced itself are stamped as they are
+        // delivered
+        let clock = self.clock;
+        event.map(|event| match event.timestamp() {
+            Some(_) => event,
+            None => event.with_timestamp(clock),
+        })
+    }
+
+    fn check_window(&self, window: Window) -> crate::Result {
+        if !self.connected {
+            Err(Error::disconnected())
+        } else if self.windows.contains_key(&window) {
+            Ok(())
+        } else {
+            Err(Error::not_our_window(window))
+        }
+    }
+
+    /// Remove a window and its descendants, queueing a `Destroyed` event
+    /// for each of them.
+    fn forget_window(&mut self, window: Window) {
+        let children: Vec<Window> = self
+            .windows
+            .iter()
+            .filter(|(_, props)| props.parent == Some(window))
+            .map(|(&child, _)| child)
+            .collect();
+
+        // children are destroyed before their parents
+        for child in children {
+            self.forget_window(child);
+        }
+
+        if self.windows.remove(&window).is_some() {
+            self.stacking.retain(|&w| w != window);
+            self.pending.remove(&window);
+            self.layout.remove(window);
+            self.exposes.set_handler(window, None);
+            self.invalidations.remove(window);
+            self.metrics.remove(window);
+            self.hovers.remove(window);
+            self.crossings.remove(window);
+            self.carets.remove(&window);
+            self.user_data.remove(&window);
+            self.titlebar_themes.remove(&window);
+            self.controls.remove(&window);
+            self.hit_tests.set_handler(window, None);
+            if let Some(key) = self.gcs.release(window) {
+                self.log.push(LogEntry::FreeGc(key));
+            }
+            self.events
+                .push(Event::new(Some(window), EventType::Destroyed));
+        }
+    }
+
+    fn restack(&mut self, window: Window, index: impl FnOnce(&[Window]) -> usize) {
+        self.stacking.retain(|&w| w != window);
+        let index = index(&self.stacking);
+        self.stacking.insert(index, window);
+    }
+
+    /// Add a window with the given properties.
+    fn insert_window(&mut self, mut props: WindowProps) -> crate::Result<Window> {
+        if !self.connected {
+            return Err(Error::disconnected());
+        }
+
+        // only screens with a compositor offer a visual with alpha
+        if props.visual_preference == VisualPreference::Rgba32 && !self.capabilities.transparency {
+            return Err(Error::unsupported("VisualPreference::Rgba32"));
+        }
+
+        if let Some(parent) = props.parent {
+            self.check_window(parent)?;
+        }
+
+        let window = Window::new(self.next_window);
+        self.next_window = self
+            .next_window
+            .checked_add(1)
+            .ok_or_else(|| Error::static_msg("Ran out of window IDs"))?;
+
+        if props.app_id.is_none() {
+            props.app_id = self.app_id.clone();
+        }
+
+        self.stacking.push(window);
+        self.windows.insert(window, props);
+        Ok(window)
+    }
+
+    /// Get the graphics context a window presents with, creating it if
+    /// no window with the same visual and depth has one.
+    ///
+    /// The simulated screen has one visual for each depth, identified by
+    /// the depth.
+    fn acquire_gc(&mut self, window: Window) -> crate::Result {
+        // windows with the default visual use their parent's
+        let mut current = self.windows.get(&window);
+        let depth = loop {
+            match current.map(|props| (props.visual_preference, props.parent)) {
+                Some((VisualPreference::Default, parent)) => {
+                    current = parent.and_then(|parent| self.windows.get(&parent));
+                }
+                Some((VisualPreference::Rgba32, _)) => break 32,
+                Some((VisualPreference::Depth(depth), _)) => break depth,
+                None => break 24,
+            }
+        };
+
+        let log = &mut self.log;
+        let (_, freed) = self
+            .gcs
+            .acquire(window, GcKey::new(depth.into(), depth), |key| {
+                log.push(LogEntry::CreateGc(key));
+                Ok::<_, Error>(key)
+            })?;
+        if let Some(key) = freed {
+            self.log.push(LogEntry::FreeGc(key));
+        }
+        Ok(())
+    }
+
+    /// Free the images evicted from the image cache.
+    fn free_evicted_images(&mut self) {
+        for id in self.images.drain_evicted() {
+            self.log.push(LogEntry::FreeImage(id));
+        }
+    }
+
+    /// Log a call concerning `window`, if it is valid.
+    ///
+    /// This does not undo anything, so calls that change state check
+    /// the window before changing it.
+    fn record(&mut self, window: Window, entry: LogEntry) -> RuntimeFuture<'_> {
+        let result = self.check_window(window);
+        if result.is_ok() {
+            self.log.push(entry);
+        }
+        ready(result)
+    }
+}
+
+impl Runtime for TestRuntime {
+    fn capabilities(&mut self) -> RuntimeFuture<'_, Capabilities> {
+        ready(Ok(self.capabilities))
+    }
+
+    fn screens(&mut self) -> RuntimeFuture<'_, Vec<ScreenInfo>> {
+        ready(Ok(self.screens.clone()))
+    }
+
+    fn system_theme(&mut self) -> RuntimeFuture<'_, Theme> {
+        ready(Ok(self.theme))
+    }
+
+    fn shutdown(&mut self) -> RuntimeFuture<'_, ShutdownReport> {
+        let report = ShutdownReport {
+            leaked_windows: self.windows.len(),
+            freed_gcs: self.gcs.drain().count(),
+            pending_events: self.events.len(),
+        };
+
+        self.windows.clear();
+        self.stacking.clear();
+        self.pending.clear();
+        self.events.clear();
+        self.redraws.clear();
+        self.carets.clear();
+        self.user_data.clear();
+        self.titlebar_themes.clear();
+        self.controls.clear();
+        self.hit_tests.clear();
+        self.native_filters.clear();
+        self.exposes = ExposeDispatcher::new();
+        self.invalidations.clear();
+        self.hovers = HoverTracker::new();
+        self.idle.clear();
+        self.tasks.clear();
+        self.crossings = CrossingTracker::new();
+        self.layout = AutoLayout::new();
+        self.images.purge();
+        self.free_evicted_images();
+        self.log.push(LogEntry::Shutdown(report));
+        ready(Ok(report))
+    }
+
+    fn set_app_id(&mut self, app_id: &str) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetAppId(app_id.into()));
+        self.app_id = Some(app_id.into());
+        ready(Ok(()))
+    }
+
+    fn set_session_id(&mut self, client_id: &str) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetSessionId(client_id.into()));
+        ready(Ok(()))
+    }
+
+    fn session_end_response(&mut self, proceed: bool) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SessionEndResponse(proceed));
+        ready(Ok(()))
+    }
+
+    fn next_event(&mut self) -> RuntimeFuture<'_, Event> {
+        let event = self.take_event();
+        ready(event.ok_or_else(|| {
+            if self.connected {
+                Error::static_msg("No events have been injected")
+            } else {
+                Error::disconnected()
+            }
+        }))
+    }
+
+    fn inject_event(&mut self, event: Event) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::InjectEvent(event.clone()));
+        let event = match event.timestamp() {
+            Some(_) => event,
+            None => event.with_timestamp(self.clock),
+        };
+
+        // timers that would have fired before this event do so first
+        if let Some(timestamp) = event.timestamp() {
+            self.clock = self.clock.max(timestamp);
+            self.fire_hovers(timestamp);
+        }
+
+        // exposures and resizes go through the same handling a real
+        // backend uses
+        let event = match (event.window(), event.ty()) {
+            (Some(window), EventType::Expose(rect)) => self.exposes.dispatch(window, *rect),
+            (
+                Some(window),
+                &EventType::MouseButtonDown {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                },
+            ) => {
+                // emulate the window manager's handling of custom chrome,
+                // like the X11 backend does
+                match self.hit_tests.test(window, x, y) {
+                    HitTestResult::Caption => {
+                        self.log.push(LogEntry::BeginMove(window));
+                        None
+                    }
+                    HitTestResult::ResizeBorder(edge) => {
+                        self.log.push(LogEntry::BeginResize(window, edge));
+                        None
+                    }
+                    _ => Some(event),
+                }
+            }
+            (window, EventType::KeyDown(key)) => match self.shortcuts.find(key) {
+                Some((id, _)) => Some(Event::new(window, EventType::Shortcut(id))),
+                None => Some(event),
+            },
+            (Some(window), EventType::Destroyed) => {
+                // the window was destroyed externally, so clean up after
+                // it and its children
+                self.forget_window(window);
+                None
+            }
+            (Some(window), &EventType::Resized { width, height }) => {
+                if let Some(props) = self.windows.get_mut(&window) {
+                    props.geometry.width = width;
+                    props.geometry.height = height;
+                }
+
+                for (child, geometry) in self.layout.on_resized(window, width, height) {
+                    if let Some(props) = self.windows.get_mut(&child) {
+                        props.geometry = geometry;
+                    }
+                }
+
+                Some(event)
+            }
+            _ => Some(event),
+        };
+
+        if let Some(event) = event {
+            self.hovers.track(&event);
+            let click = self.clicks.track(&event);
+            self.events.push(event);
+            if let Some(click) = click {
+                self.events.push(click);
+            }
+        }
+        ready(Ok(()))
+    }
+
+    fn spawn_task(&mut self, task: Task) -> RuntimeFuture<'_> {
+        self.tasks.spawn(task);
+        ready(Ok(()))
+    }
+
+    fn on_idle(&mut self, callback: IdleCallback) -> RuntimeFuture<'_, IdleId> {
+        let id = self.idle.add(callback);
+        self.log.push(LogEntry::OnIdle(id));
+        ready(Ok(id))
+    }
+
+    fn cancel_idle(&mut self, id: IdleId) -> RuntimeFuture<'_> {
+        self.idle.remove(id);
+        self.log.push(LogEntry::CancelIdle(id));
+        ready(Ok(()))
+    }
+
+    fn set_double_click_interval(&mut self, interval: Duration) -> RuntimeFuture<'_> {
+        self.clicks.set_interval(interval);
+        self.log.push(LogEntry::SetDoubleClickInterval(interval));
+        ready(Ok(()))
+    }
+
+    fn set_queue_policy(&mut self, policy: QueuePolicy) -> RuntimeFuture<'_> {
+        let result = self.events.set_policy(policy);
+        if result.is_ok() {
+            self.log.push(LogEntry::SetQueuePolicy(policy));
+        }
+        ready(result)
+    }
+
+    fn register_client_message(&mut self, name: &str) -> RuntimeFuture<'_, u32> {
+        // atoms below this one are predefined by the X11 protocol
+        const FIRST_ATOM: u32 = 69;
+
+        let next = FIRST_ATOM + self.client_messages.len() as u32;
+        let atom = *self.client_messages.entry(name.into()).or_insert(next);
+        self.log
+            .push(LogEntry::RegisterClientMessage(name.into(), atom));
+        ready(Ok(atom))
+    }
+
+    fn send_client_message(&mut self, window: Window, message: ClientMessage) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SendClientMessage(window, message));
+        self.receive_client_message(window, message);
+        ready(Ok(()))
+    }
+
+    fn add_native_event_filter(
+        &mut self,
+        filter: NativeEventFilter,
+    ) -> RuntimeFuture<'_, NativeEventFilterId> {
+        let id = self.native_filters.add(filter);
+        self.log.push(LogEntry::AddNativeEventFilter(id));
+        ready(Ok(id))
+    }
+
+    fn remove_native_event_filter(&mut self, id: NativeEventFilterId) -> RuntimeFuture<'_> {
+        self.native_filters.remove(id);
+        self.log.push(LogEntry::RemoveNativeEventFilter(id));
+        ready(Ok(()))
+    }
+
+    fn is_alive(&mut self) -> RuntimeFuture<'_, bool> {
+        ready(Ok(self.connected))
+    }
+
+    fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetReconnectPolicy(policy));
+        self.reconnect = policy;
+        ready(Ok(()))
+    }
+
+    fn set_image_cache_budget(&mut self, bytes: usize) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetImageCacheBudget(bytes));
+        self.images.set_budget(bytes);
+        self.free_evicted_images();
+        ready(Ok(()))
+    }
+
+    fn purge_image_cache(&mut self) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::PurgeImageCache);
+        self.images.purge();
+        self.free_evicted_images();
+        ready(Ok(()))
+    }
+
+    fn set_paint_workers(&mut self, count: usize) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetPaintWorkers(count));
+        ready(Ok(()))
+    }
+
+    fn set_pacing_policy(&mut self, policy: PacingPolicy) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetPacingPolicy(policy));
+        self.exposes.set_policy(policy);
+        ready(Ok(()))
+    }
+
+    fn metrics(&mut self) -> RuntimeFuture<'_, Metrics> {
+        ready(Ok(self.metrics.metrics(self.events.len())))
+    }
+
+    fn paint_stats(&mut self) -> RuntimeFuture<'_, PaintStats> {
+        ready(Ok(self.exposes.stats()))
+    }
+
+    fn window_set_paint_handler(
+        &mut self,
+        window: Window,
+        handler: Option<PaintHandler>,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.log
+            .push(LogEntry::SetPaintHandler(window, handler.is_some()));
+        self.exposes.set_handler(window, handler);
+        ready(Ok(()))
+    }
+
+    fn set_auto_layout(&mut self, enabled: bool) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::SetAutoLayout(enabled));
+        self.layout.set_enabled(enabled);
+        ready(Ok(()))
+    }
+
+    fn window_set_layout(
+        &mut self,
+        window: Window,
+        layout: Option<ChildLayout>,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        match self.windows[&window].parent {
+            Some(parent) => {
+                self.layout.set_layout(parent, window, layout);
+                self.record(window, LogEntry::SetLayout(window, layout))
+            }
+            None => ready(Err(Error::static_msg(
+                "Layouts can only be attached to child windows",
+            ))),
+        }
+    }
+
+    fn register_shortcut(
+        &mut self,
+        key: KeyInfo,
+        scope: ShortcutScope,
+    ) -> RuntimeFuture<'_, ShortcutId> {
+        let result = self.shortcuts.register(key, scope);
+        if let Ok(id) = result {
+            self.log.push(LogEntry::RegisterShortcut(id, key, scope));
+        }
+        ready(result)
+    }
+
+    fn unregister_shortcut(&mut self, id: ShortcutId) -> RuntimeFuture<'_> {
+        if self.shortcuts.unregister(id).is_none() {
+            return ready(Err(Error::static_msg(
+                "Keyboard shortcut is not registered",
+            )));
+        }
+
+        self.log.push(LogEntry::UnregisterShortcut(id));
+        ready(Ok(()))
+    }
+
+    fn create_window(&mut self, props: WindowProps) -> RuntimeFuture<'_, Window> {
+        let logged = props.clone();
+        let result = self.insert_window(props);
+        if let Ok(window) = result {
+            self.log.push(LogEntry::CreateWindow(window, logged));
+        }
+        ready(result)
+    }
+
+    fn create_control(
+        &mut self,
+        kind: ControlKind,
+        props: WindowProps,
+    ) -> RuntimeFuture<'_, Window> {
+        if props.parent.is_none() {
+            return ready(Err(Error::static_msg("Controls must have a parent window")));
+        }
+
+        let result = self.insert_window(props);
+        if let Ok(window) = result {
+            self.controls.insert(window, kind);
+            self.log.push(LogEntry::CreateControl(window, kind));
+        }
+        ready(result)
+    }
+
+    fn control_text(&mut self, window: Window) -> RuntimeFuture<'_, String> {
+        let text = match (
+            self.controls.contains_key(&window),
+            self.windows.get(&window),
+        ) {
+            (true, Some(props)) => Ok(props.title.clone()),
+            (false, Some(_)) => Err(Error::static_msg("Window is not a control")),
+            _ => Err(Error::not_our_window(window)),
+        };
+        ready(text)
+    }
+
+    fn destroy_window(&mut self, window: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.log.push(LogEntry::DestroyWindow(window));
+        self.forget_window(window);
+        ready(Ok(()))
+    }
+
+    fn flush(&mut self) -> RuntimeFuture<'_> {
+        for (window, config) in core::mem::take(&mut self.pending) {
+            let props = match self.windows.get_mut(&window) {
+                Some(props) => props,
+                None => continue,
+            };
+
+            if let Some((x, y)) = config.position {
+                props.geometry.x = x;
+                props.geometry.y = y;
+            }
+            if let Some((width, height)) = config.size {
+                props.geometry.width = width;
+                props.geometry.height = height;
+            }
+            if let Some(visibility) = config.visibility {
+                props.visibility = visibility;
+            }
+            if let Some(title) = config.title {
+                props.title = title;
+            }
+        }
+
+        self.log.push(LogEntry::Flush);
+        ready(Ok(()))
+    }
+
+    fn clipboard_formats(
+        &mut self,
+        selection: Selection,
+    ) -> RuntimeFuture<'_, Vec<ClipboardFormat>> {
+        let contents = self.selections.get(&selection).map_or(&[][..], |c| &c[..]);
+        ready(Ok(contents.iter().map(ClipboardContent::format).collect()))
+    }
+
+    fn clipboard_read(
+        &mut self,
+        selection: Selection,
+        format: ClipboardFormat,
+    ) -> RuntimeFuture<'_, ClipboardContent> {
+        ready(
+            self.selections
+                .get(&selection)
+                .and_then(|contents| contents.iter().find(|content| content.format() == format))
+                .cloned()
+                .ok_or_else(|| Error::static_msg("The selection has no data in that format")),
+        )
+    }
+
+    fn clipboard_write(
+        &mut self,
+        selection: Selection,
+        contents: Vec<ClipboardContent>,
+    ) -> RuntimeFuture<'_> {
+        self.log
+            .push(LogEntry::ClipboardWrite(selection, contents.clone()));
+        self.selections.insert(selection, contents);
+        ready(Ok(()))
+    }
+
+    fn windows(&mut self) -> RuntimeFuture<'_, Vec<Window>> {
+        ready(Ok(self.windows.keys().copied().collect()))
+    }
+
+    fn query_window(&mut self, window: Window) -> RuntimeFuture<'_, WindowInfo> {
+        ready(
+            self.windows
+                .get(&window)
+                .map(|props| WindowInfo {
+                    geometry: props.geometry,
+                    visibility: props.visibility,
+                    parent: props.parent,
+                    state: WindowState::Normal,
+                })
+                .ok_or_else(|| Error::not_our_window(window)),
+        )
+    }
+
+    fn window_scale_factor(&mut self, window: Window) -> RuntimeFuture<'_, f64> {
+        // use the screen containing the top-level window's top-left
+        // corner, or the primary screen if it is off-screen
+        let result = self.check_window(window).map(|()| {
+            let mut top_level = &self.windows[&window];
+            while let Some(parent) = top_level.parent.and_then(|p| self.windows.get(&p)) {
+                top_level = parent;
+            }
+            let geometry = top_level.geometry;
+            let on_screen = |screen: &&ScreenInfo| screen.geometry.contains(geometry.x, geometry.y);
+
+            self.screens
+                .iter()
+                .find(on_screen)
+                .or_else(|| self.screens.iter().find(|screen| screen.primary))
+                .map_or(1.0, ScreenInfo::scale_factor)
+        });
+        ready(result)
+    }
+
+    fn window_set_geometry(&mut self, window: Window, geometry: Rectangle) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.geometry = geometry;
+        }
+
+        self.record(window, LogEntry::SetGeometry(window, geometry))
+    }
+
+    fn window_request_redraw(&mut self, window: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        // there is no monitor to wait for, so the redraw is due
+        // immediately, unless one is already queued
+        if self.redraws.insert(window) {
+            self.events
+                .push(Event::new(Some(window), EventType::RedrawRequested));
+        }
+
+        self.record(window, LogEntry::RequestRedraw(window))
+    }
+
+    fn window_invalidate(&mut self, window: Window, rect: Rectangle) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.invalidations.invalidate(window, rect);
+
+        self.record(window, LogEntry::Invalidate(window, rect))
+    }
+
+    fn window_present_with_damage(
+        &mut self,
+        window: Window,
+        damage: &[Rectangle],
+    ) -> RuntimeFuture<'_> {
+        let result = self
+            .check_window(window)
+            .and_then(|()| self.acquire_gc(window));
+        if result.is_ok() {
+            self.log.push(LogEntry::Present(window, damage.to_vec()));
+            self.metrics.end_paint(window, self.clock);
+        }
+        ready(result)
+    }
+
+    fn window_present_frame(
+        &mut self,
+        window: Window,
+        frame: &VideoFrame,
+        dest: Rectangle,
+    ) -> RuntimeFuture<'_> {
+        let result = self
+            .check_window(window)
+            .and_then(|()| self.acquire_gc(window));
+        if result.is_ok() {
+            self.log
+                .push(LogEntry::PresentFrame(window, frame.clone(), dest));
+            self.metrics.end_paint(window, self.clock);
+        }
+        ready(result)
+    }
+
+    fn window_set_title(&mut self, window: Window, title: &str) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.title = title.into();
+        }
+
+        self.record(window, LogEntry::SetTitle(window, title.into()))
+    }
+
+    fn window_configure_batch(
+        &mut self,
+        window: Window,
+        config: WindowConfig,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.pending
+            .entry(window)
+            .or_default()
+            .merge(config.clone());
+
+        self.record(window, LogEntry::ConfigureBatch(window, config))
+    }
+
+    fn window_reparent(
+        &mut self,
+        window: Window,
+        new_parent: Window,
+        x: i32,
+        y: i32,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window).and(self.check_window(new_parent)) {
+            return ready(Err(err));
+        }
+
+        // a window can't become its own ancestor
+        let mut ancestor = Some(new_parent);
+        while let Some(current) = ancestor {
+            if current == window {
+                return ready(Err(Error::static_msg(
+                    "Windows cannot be reparented into themselves",
+                )));
+            }
+            ancestor = self.windows.get(&current).and_then(|props| props.parent);
+        }
+
+        // layouts are relative to the old parent
+        self.layout.remove_child(window);
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.parent = Some(new_parent);
+            props.geometry.x = x;
+            props.geometry.y = y;
+        }
+
+        self.record(window, LogEntry::Reparent(window, new_parent, x, y))
+    }
+
+    fn window_raise(&mut self, window: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.restack(window, |stacking| stacking.len());
+
+        self.record(window, LogEntry::Restack(window))
+    }
+
+    fn window_activate(&mut self, window: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            if props.visibility == Visibility::Iconified {
+                props.visibility = Visibility::Visible;
+            }
+        }
+        self.restack(window, |stacking| stacking.len());
+
+        self.record(window, LogEntry::Activate(window))
+    }
+
+    fn window_begin_interactive_move(&mut self, window: Window) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::BeginMove(window))
+    }
+
+    fn window_begin_interactive_resize(
+        &mut self,
+        window: Window,
+        edge: ResizeEdge,
+    ) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::BeginResize(window, edge))
+    }
+
+    fn window_set_hit_test(
+        &mut self,
+        window: Window,
+        handler: Option<HitTestHandler>,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        let set = handler.is_some();
+        self.hit_tests.set_handler(window, handler);
+
+        self.record(window, LogEntry::SetHitTest(window, set))
+    }
+
+    fn window_lower(&mut self, window: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.restack(window, |_| 0);
+
+        self.record(window, LogEntry::Restack(window))
+    }
+
+    fn window_restack_above(&mut self, window: Window, sibling: Window) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        let parent = |w| self.windows.get(&w).map(|props| props.parent);
+        match (parent(window), parent(sibling)) {
+            (Some(a), Some(b)) if a == b && window != sibling => {}
+            (Some(_), Some(_)) => {
+                return ready(Err(Error::static_msg(
+                    "Windows can only be restacked above their siblings",
+                )))
+            }
+            (None, _) => return ready(Err(Error::not_our_window(window))),
+            (_, None) => return ready(Err(Error::not_our_window(sibling))),
+        }
+
+        self.restack(window, |stacking| {
+            stacking
+                .iter()
+                .position(|&w| w == sibling)
+                .map_or(stacking.len(), |index| index + 1)
+        });
+        self.record(window, LogEntry::Restack(window))
+    }
+
+    fn window_set_visibility(
+        &mut self,
+        window: Window,
+        visibility: Visibility,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.visibility = visibility;
+        }
+
+        self.record(window, LogEntry::SetVisibility(window, visibility))
+    }
+
+    fn window_set_caret(&mut self, window: Window, caret: Option<Rectangle>) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        let old = self.carets.entry(window).or_default().set_rect(caret);
+        for rect in old.into_iter().chain(caret) {
+            if let Some(event) = self.exposes.dispatch(window, rect) {
+                self.events.push(event);
+            }
+        }
+
+        self.record(window, LogEntry::SetCaret(window, caret))
+    }
+
+    fn window_caret_visible(&mut self, window: Window) -> RuntimeFuture<'_, bool> {
+        let caret = self.carets.get(&window).copied().unwrap_or_default();
+        ready(self.check_window(window).map(|()| caret.is_visible()))
+    }
+
+    fn window_set_titlebar_theme(
+        &mut self,
+        window: Window,
+        theme: Option<Theme>,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        match theme {
+            Some(theme) => {
+                self.titlebar_themes.insert(window, theme);
+            }
+            None => {
+                self.titlebar_themes.remove(&window);
+            }
+        }
+
+        self.record(window, LogEntry::SetTitlebarTheme(window, theme))
+    }
+
+    fn window_set_alpha(&mut self, window: Window, alpha: f32) -> RuntimeFuture<'_> {
+        if alpha.is_nan() {
+            return ready(Err(Error::static_msg("Window alpha is not a number")));
+        }
+
+        self.record(window, LogEntry::SetAlpha(window, alpha.clamp(0.0, 1.0)))
+    }
+
+    fn window_request_attention(
+        &mut self,
+        window: Window,
+        attention: AttentionType,
+    ) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::RequestAttention(window, attention))
+    }
+
+    fn window_set_skip_taskbar(&mut self, window: Window, skip: bool) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.skip_taskbar = skip;
+        }
+
+        self.record(window, LogEntry::SetSkipTaskbar(window, skip))
+    }
+
+    fn window_set_user_data(
+        &mut self,
+        window: Window,
+        data: Option<UserData>,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.log.push(LogEntry::SetUserData(window, data.is_some()));
+        match data {
+            Some(data) => self.user_data.insert(window, data),
+            None => self.user_data.remove(&window),
+        };
+        ready(Ok(()))
+    }
+
+    fn window_user_data(&mut self, window: Window) -> RuntimeFuture<'_, Option<UserData>> {
+        let data = self.user_data.get(&window).cloned();
+        ready(self.check_window(window).map(|()| data))
+    }
+
+    fn window_embed_foreign(
+        &mut self,
+        window: Window,
+        foreign: NativeHandle,
+        geometry: Rectangle,
+    ) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::EmbedForeign(window, foreign, geometry))
+    }
+
+    fn window_set_event_mask(&mut self, window: Window, mask: EventMask) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Some(props) = self.windows.get_mut(&window) {
+            props.event_mask = mask;
+        }
+
+        self.record(window, LogEntry::SetEventMask(window, mask))
+    }
+
+    fn window_set_hover_config(
+        &mut self,
+        window: Window,
+        config: HoverConfig,
+    ) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        self.hovers.set_config(window, config);
+
+        self.record(window, LogEntry::SetHoverConfig(window, config))
+    }
+
+    fn window_set_cursor(&mut self, window: Window, cursor: Cursor) -> RuntimeFuture<'_> {
+        if let Err(err) = self.check_window(window) {
+            return ready(Err(err));
+        }
+
+        if let Cursor::Custom(ref custom) = cursor {
+            let log = &mut self.log;
+            let uploaded = self.images.get_or_create(custom.image(), |image| {
+                log.push(LogEntry::UploadImage(image.id()));
+                Ok::<_, Error>(image.id())
+            });
+            if let Err(err) = uploaded {
+                return ready(Err(err));
+            }
+            self.free_evicted_images();
+        }
+
+        self.record(window, LogEntry::SetCursor(window, cursor))
+    }
+
+    fn window_set_menu_bar(&mut self, window: Window, menu: Option<Menu>) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::SetMenuBar(window, menu))
+    }
+
+    fn window_set_access_tree(
+        &mut self,
+        window: Window,
+        tree: Option<AccessNode>,
+    ) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::SetAccessTree(window, tree))
+    }
+
+    fn access_announce(&mut self, text: &str, priority: Priority) -> RuntimeFuture<'_> {
+        self.log.push(LogEntry::Announce(text.into(), priority));
+        ready(Ok(()))
+    }
+
+    fn window_popup_context_menu(
+        &mut self,
+        window: Window,
+        menu: Menu,
+        x: i32,
+        y: i32,
+    ) -> RuntimeFuture<'_> {
+        self.record(window, LogEntry::PopupContextMenu(window, menu, x, y))
+    }
+}
@@ -0,0 +1,287 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{LogEntry, TestRuntime};
+use crate::{
+    async_runtime::Runtime, Error, Event, EventType, Key, KeyInfo, Modifiers, MouseButton, Result,
+    Window,
+};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{fmt, time::Duration};
+
+/// A sequence of input, clock steps and assertions to run against a
+/// `TestRuntime`.
+///
+/// Input is injected through `Runtime::inject_event`, so the runtime
+/// handles it exactly as it would handle input from the user. Event
+/// assertions receive the events the runtime delivers, like an
+/// application would.
+///
+/// ```ignore
+/// Script::new()
+///     .click(100, 100)
+///     .advance(Duration::from_millis(100))
+///     .click(100, 100)
+///     .expect_event(EventType::MouseDoubleClick { x: 100, y: 100, button: MouseButton::Left })
+///     .expect_window_count(2)
+///     .run(&mut runtime)
+///     .await?;
+/// ```
+#[derive(Default)]
+pub struct Script {
+    window: Option<Window>,
+    steps: Vec<Step>,
+}
+
+enum Step {
+    /// Inject an event.
+    Inject(Event),
+    /// Advance the runtime's simulated clock.
+    Advance(Duration),
+    /// Assert that the number of windows matches.
+    ExpectWindowCount(usize),
+    /// Assert that an arbitrary predicate holds over the windows.
+    Expect(String, Predicate),
+    /// Assert that a predicate holds over the events delivered so far.
+    ExpectEvents(String, EventPredicate),
+    /// Assert that a predicate holds over the runtime's log.
+    ExpectLog(String, LogPredicate),
+}
+
+type Predicate = Box<dyn FnMut(&[Window]) -> bool + Send>;
+type EventPredicate = Box<dyn FnMut(&[Event]) -> bool + Send>;
+type LogPredicate = Box<dyn FnMut(&[LogEntry]) -> bool + Send>;
+
+impl fmt::Debug for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Inject(event) => f.debug_tuple("Inject").field(event).finish(),
+            Step::ExpectWindowCount(count) => {
+                f.debug_tuple("ExpectWindowCount").field(count).finish()
+            }
+            Step::Advance(elapsed) => f.debug_tuple("Advance").field(elapsed).finish(),
+            Step::Expect(name, _) => f.debug_tuple("Expect").field(name).finish(),
+            Step::ExpectEvents(name, _) => f.debug_tuple("ExpectEvents").field(name).finish(),
+            Step::ExpectLog(name, _) => f.debug_tuple("ExpectLog").field(name).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.steps).finish()
+    }
+}
+
+impl Script {
+    /// Create a new, empty `Script`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Target subsequent events at the given window.
+    pub fn target(mut self, window: Window) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Inject an arbitrary event into the runtime.
+    pub fn event(mut self, ty: EventType) -> Self {
+        self.steps.push(Step::Inject(Event::new(self.window, ty)));
+        self
+    }
+
+    /// Press and release the left mouse button at the given coordinates.
+    pub fn click(self, x: i32, y: i32) -> Self {
+        let button = MouseButton::Left;
+        self.event(EventType::MouseButtonDown { x, y, button })
+            .event(EventType::MouseButtonUp { x, y, button })
+    }
+
+    /// Press and release a key, with the given modifiers held.
+    pub fn key(self, key: Key, modifiers: Modifiers) -> Self {
+        let info = KeyInfo::new(key, modifiers);
+        self.event(EventType::KeyDown(info))
+            .event(EventType::KeyUp(info))
+    }
+
+    /// Advance the runtime's simulated clock, e.g. to separate two clicks
+    /// or to let a hover delay pass.
+    ///
+    /// Events injected afterwards are timestamped with the new time.
+    pub fn advance(mut self, elapsed: Duration) -> Self {
+        self.steps.push(Step::Advance(elapsed));
+        self
+    }
+
+    /// Scroll the mouse wheel with the pointer at the given coordinates.
+    pub fn scroll(self, x: i32, y: i32, delta_x: f32, delta_y: f32) -> Self {
+        self.event(EventType::MouseWheel {
+            x,
+            y,
+            delta_x,
+            delta_y,
+        })
+    }
+
+    /// Type out the given text, one key at a time.
+    ///
+    /// Like a real keyboard, each printable character produces an
+    /// `EventType::TextInput` between the key's press and release.
+    pub fn type_text(self, text: &str) -> Self {
+        text.chars().fold(self, |script, c| {
+            let (key, modifiers) = match c {
+                ' ' => (Key::Space, Modifiers::NONE),
+                '\n' => (Key::Enter, Modifiers::NONE),
+                '\t' => (Key::Tab, Modifiers::NONE),
+                c if c.is_uppercase() => (Key::Char(c), Modifiers::SHIFT),
+                c => (Key::Char(c), Modifiers::NONE),
+            };
+            if c.is_control() {
+                return script.key(key, modifiers);
+            }
+
+            let info = KeyInfo::new(key, modifiers);
+            script
+                .event(EventType::KeyDown(info))
+                .event(EventType::TextInput(c.into()))
+                .event(EventType::KeyUp(info))
+        })
+    }
+
+    /// Assert that the runtime has exactly `count` windows at this point.
+    pub fn expect_window_count(mut self, count: usize) -> Self {
+        self.steps.push(Step::ExpectWindowCount(count));
+        self
+    }
+
+    /// Assert that `predicate` holds for the runtime's windows at this
+    /// point.
+    pub fn expect(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl FnMut(&[Window]) -> bool + Send + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::Expect(name.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Assert that an event of the given type has been delivered by this
+    /// point.
+    pub fn expect_event(self, ty: EventType) -> Self {
+        let name = format!("an event of type {:?}", &ty);
+        self.expect_events(name, move |events| {
+            events.iter().any(|event| *event.ty() == ty)
+        })
+    }
+
+    /// Assert that `predicate` holds for the events delivered by this
+    /// point, in order.
+    ///
+    /// Events are received from the runtime with `Runtime::next_event`
+    /// as the script runs, so only events that the windows' masks select
+    /// are seen.
+    pub fn expect_events(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl FnMut(&[Event]) -> bool + Send + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::ExpectEvents(name.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Assert that `predicate` holds for the runtime's log at this point.
+    pub fn expect_log(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl FnMut(&[LogEntry]) -> bool + Send + 'static,
+    ) -> Self {
+        self.steps
+            .push(Step::ExpectLog(name.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Run this script against the given runtime.
+    ///
+    /// Errors from the runtime are returned. So is the first failed
+    /// expectation, as an error describing the step that failed, and the
+    /// rest of the script is not run.
+    pub async fn run(self, runtime: &mut TestRuntime) -> Result {
+        let mut delivered = Vec::new();
+
+        for (index, step) in self.steps.into_iter().enumerate() {
+            tracing::trace!("Running script step {}: {:?}", index, &step);
+
+            match step {
+                Step::Inject(event) => runtime.inject_event(event).await?,
+                Step::Advance(elapsed) => runtime.advance_time(elapsed),
+                Step::ExpectWindowCount(count) => {
+                    let windows = runtime.windows().await?;
+                    if windows.len() != count {
+                        return Err(Error::msg(format!(
+                            "script step {}: expected {} windows, found {}",
+                            index,
+                            count,
+                            windows.len()
+                        )));
+                    }
+                }
+                Step::Expect(name, mut predicate) => {
+                    let windows = runtime.windows().await?;
+                    if !predicate(&windows) {
+                        return Err(failed(index, &name, &windows));
+                    }
+                }
+                Step::ExpectEvents(name, mut predicate) => {
+                    receive(runtime, &mut delivered);
+                    if !predicate(&delivered) {
+                        return Err(failed(index, &name, &delivered));
+                    }
+                }
+                Step::ExpectLog(name, mut predicate) => {
+                    if !predicate(runtime.log()) {
+                        return Err(failed(index, &name, runtime.log()));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Receive every event the runtime has queued.
+fn receive(runtime: &mut TestRuntime, delivered: &mut Vec<Event>) {
+    while runtime.pending_events() > 0 {
+        match runtime.take_event() {
+            Some(event) => delivered.push(event),
+            // the rest were not selected by their windows' masks
+            None => break,
+        }
+    }
+}
+
+/// Describe a failed expectation, along with what it was checked
+/// against.
+fn failed<T: fmt::Debug + ?Sized>(index: usize, name: &str, actual: &T) -> Error {
+    Error::msg(format!(
+        "script step {}: expectation \"{}\" failed, with {:?}",
+        index, name, actual
+    ))
+}
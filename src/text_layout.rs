@@ -0,0 +1,494 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{color::Rgba, font::FontWeight, Rectangle};
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+/// The measurements of a font needed to lay out text.
+///
+/// Backends implement this for the fonts they load, e.g. with
+/// `XftTextExtents` on X11 and `GetCharABCWidths` on Windows.
+pub trait FontMetrics {
+    /// The distance from the baseline to the top of the tallest glyph.
+    fn ascent(&self) -> f32;
+
+    /// The distance from the baseline to the bottom of the lowest glyph.
+    fn descent(&self) -> f32;
+
+    /// The extra space to leave between lines.
+    fn line_gap(&self) -> f32 {
+        0.0
+    }
+
+    /// The horizontal distance to advance after drawing a character.
+    fn advance(&self, c: char) -> f32;
+
+    /// The horizontal distance to advance after drawing a character in
+    /// the given weight.
+    ///
+    /// By default, every weight is assumed to be as wide as the normal
+    /// one.
+    #[allow(unused_variables)]
+    fn advance_with_weight(&self, c: char, weight: FontWeight) -> f32 {
+        self.advance(c)
+    }
+}
+
+/// The style that a span of text is drawn in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TextStyle {
+    /// The color of the text.
+    pub color: Rgba,
+    /// The color filled in behind the text, if any.
+    pub background: Option<Rgba>,
+    /// The weight of the font.
+    pub weight: FontWeight,
+    /// Whether or not the text is underlined.
+    pub underline: bool,
+    /// Whether or not the text is struck through.
+    pub strikethrough: bool,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle {
+            color: crate::color::colors::BLACK,
+            background: None,
+            weight: FontWeight::NORMAL,
+            underline: false,
+            strikethrough: false,
+        }
+    }
+}
+
+/// A span of text drawn in a particular style.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextSpan {
+    /// The byte range of the text that the style applies to.
+    pub range: Range<usize>,
+    /// The style to apply.
+    pub style: TextStyle,
+}
+
+/// A run of characters on one line that share a style, and so can be
+/// drawn with a single call to the windowing system.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyledRun {
+    /// The byte range of the text in this run.
+    pub range: Range<usize>,
+    /// The X coordinate of the left edge of the run.
+    pub x: f32,
+    /// The width of the run.
+    pub width: f32,
+    /// The Y coordinate of the baseline of the run.
+    pub baseline: f32,
+    /// The style of the run.
+    pub style: TextStyle,
+}
+
+/// A block of text that has been broken into lines, with the position of
+/// every character computed.
+///
+/// Positions are in pixels, relative to the top-left corner of the
+/// block. Indices are byte indices into the text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    text: String,
+    spans: Vec<TextSpan>,
+    glyphs: Vec<PositionedGlyph>,
+    lines: Vec<LayoutLine>,
+    ascent: f32,
+    descent: f32,
+    line_height: f32,
+}
+
+/// A character in a `TextLayout`, along with its position.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PositionedGlyph {
+    /// The byte index of the character in the text.
+    pub index: usize,
+    /// The line the character is on.
+    pub line: usize,
+    /// The X coordinate of the left edge of the character.
+    pub x: f32,
+    /// The horizontal advance of the character.
+    pub advance: f32,
+}
+
+/// A line in a `TextLayout`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    /// The byte range of the text on this line, including any line
+    /// break or whitespace that ends it.
+    pub range: Range<usize>,
+    /// The Y coordinate of this line's baseline.
+    pub baseline: f32,
+    /// The width of this line, not counting trailing whitespace.
+    pub width: f32,
+}
+
+impl TextLayout {
+    /// Lay out `text` with the given font.
+    ///
+    /// Lines are broken at newlines. If `max_width` is set, lines are
+    /// also wrapped at whitespace to fit within it, and words that are
+    /// too long to fit on a line of their own are broken between
+    /// characters.
+    pub fn new(text: &str, metrics: &dyn FontMetrics, max_width: Option<f32>) -> Self {
+        Self::with_spans(text, metrics, max_width, Vec::new())
+    }
+
+    /// Lay out `text` with the given font, applying styles to spans of
+    /// it.
+    ///
+    /// Text outside of every span uses the default style. Where spans
+    /// overlap, the later span takes precedence.
+    pub fn with_spans(
+        text: &str,
+        metrics: &dyn FontMetrics,
+        max_width: Option<f32>,
+        spans: Vec<TextSpan>,
+    ) -> Self {
+        let mut glyphs: Vec<PositionedGlyph> = Vec::with_capacity(text.len());
+        let mut line = 0;
+        let mut line_start = 0;
+        let mut x = 0.0;
+        // the glyph that the line can be wrapped before, after the last
+        // whitespace
+        let mut wrap_at = None;
+
+        for (index, c) in text.char_indices() {
+            if c == '\n' {
+                glyphs.push(PositionedGlyph {
+                    index,
+                    line,
+                    x,
+                    advance: 0.0,
+                });
+                line += 1;
+                line_start = glyphs.len();
+                x = 0.0;
+                wrap_at = None;
+                continue;
+            }
+
+            let advance = metrics.advance_with_weight(c, style_at(&spans, index).weight);
+
+            // whitespace is allowed to hang past the edge
+            let overflows = match max_width {
+                Some(max) => x + advance > max,
+                None => false,
+            };
+            if overflows && !c.is_whitespace() && glyphs.len() > line_start {
+                let start = wrap_at.unwrap_or(glyphs.len());
+                let offset = glyphs.get(start).map_or(x, |glyph| glyph.x);
+                line += 1;
+
+                for glyph in &mut glyphs[start..] {
+                    glyph.line = line;
+                    glyph.x -= offset;
+                }
+
+                line_start = start;
+                x -= offset;
+                wrap_at = None;
+            }
+
+            glyphs.push(PositionedGlyph {
+                index,
+                line,
+                x,
+                advance,
+            });
+            x += advance;
+
+            if c.is_whitespace() {
+                wrap_at = Some(glyphs.len());
+            }
+        }
+
+        let ascent = metrics.ascent();
+        let descent = metrics.descent();
+        let line_height = ascent + descent + metrics.line_gap();
+
+        let lines = (0..=line)
+            .map(|line| {
+                let on_line = glyphs.iter().filter(|glyph| glyph.line == line);
+                let start = on_line.clone().next().map_or(text.len(), |g| g.index);
+                let end = on_line.clone().next_back().map_or(start, |g| {
+                    g.index + text[g.index..].chars().next().map_or(0, char::len_utf8)
+                });
+                let width = on_line
+                    .filter(|g| !text[g.index..].starts_with(char::is_whitespace))
+                    .map(|g| g.x + g.advance)
+                    .fold(0.0, f32::max);
+
+                LayoutLine {
+                    range: start..end,
+                    baseline: ascent + line as f32 * line_height,
+                    width,
+                }
+            })
+            .collect();
+
+        TextLayout {
+            text: text.into(),
+            spans,
+            glyphs,
+            lines,
+            ascent,
+            descent,
+            line_height,
+        }
+    }
+
+    /// Get the text that was laid out.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the style of the character at the given index.
+    pub fn style_at(&self, index: usize) -> TextStyle {
+        style_at(&self.spans, index)
+    }
+
+    /// Split the text into runs of characters that share a line and a
+    /// style, in order.
+    ///
+    /// Line breaks are not included in any run.
+    pub fn runs(&self) -> Vec<StyledRun> {
+        let mut runs: Vec<StyledRun> = Vec::new();
+        let mut last_line = None;
+
+        for glyph in &self.glyphs {
+            let c = self.text[glyph.index..].chars().next().unwrap_or('\n');
+            if c == '\n' {
+                continue;
+            }
+
+            let style = self.style_at(glyph.index);
+            let end = glyph.index + c.len_utf8();
+            match runs.last_mut() {
+                Some(run) if last_line == Some(glyph.line) && run.style == style => {
+                    run.range.end = end;
+                    run.width = glyph.x + glyph.advance - run.x;
+                }
+                _ => runs.push(StyledRun {
+                    range: glyph.index..end,
+                    x: glyph.x,
+                    width: glyph.advance,
+                    baseline: self.lines[glyph.line].baseline,
+                    style,
+                }),
+            }
+            last_line = Some(glyph.line);
+        }
+
+        runs
+    }
+
+    /// Get the positioned characters, in the order they appear in the
+    /// text.
+    pub fn glyphs(&self) -> &[PositionedGlyph] {
+        &self.glyphs
+    }
+
+    /// Get the lines that the text was broken into.
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    /// Get the distance from each line's baseline to its top.
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    /// Get the distance from each line's baseline to its bottom.
+    pub fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    /// Get the distance between the baselines of consecutive lines.
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// Get the width and height of the laid out text.
+    pub fn size(&self) -> (f32, f32) {
+        let width = self.lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        (width, self.lines.len() as f32 * self.line_height)
+    }
+
+    /// Get the index that a caret should be placed at when the given
+    /// point is clicked.
+    ///
+    /// Points outside of the text are clamped to the nearest line, and
+    /// to the start or end of that line.
+    pub fn position_at_point(&self, x: f32, y: f32) -> usize {
+        let line = if y <= 0.0 || self.line_height <= 0.0 {
+            0
+        } else {
+            ((y / self.line_height) as usize).min(self.lines.len() - 1)
+        };
+
+        let mut on_line = self.glyphs.iter().filter(|glyph| glyph.line == line);
+        match on_line.find(|glyph| x < glyph.x + glyph.advance / 2.0) {
+            Some(glyph) => glyph.index,
+            None => self.line_end(line),
+        }
+    }
+
+    /// Get the rectangle that a caret at the given index should be drawn
+    /// in, rounded to whole pixels.
+    ///
+    /// Indices that are not on a character boundary are moved back to
+    /// the previous one.
+    pub fn caret_rect_for_index(&self, index: usize) -> Rectangle {
+        let (line, x) = match self.glyphs.iter().rev().find(|g| g.index <= index) {
+            Some(glyph) if glyph.index == index || index < self.text.len() => (glyph.line, glyph.x),
+            // the caret is after the last character
+            Some(glyph) if self.text[glyph.index..].starts_with('\n') => (glyph.line + 1, 0.0),
+            Some(glyph) => (glyph.line, glyph.x + glyph.advance),
+            None => (0, 0.0),
+        };
+
+        let top = line as f32 * self.line_height;
+        Rectangle::new(
+            round(x),
+            round(top),
+            1,
+            round(self.ascent + self.descent).max(1) as u32,
+        )
+    }
+
+    /// Get the index of the end of a line, before any line break.
+    fn line_end(&self, line: usize) -> usize {
+        let range = &self.lines[line].range;
+        if self.text[range.clone()].ends_with('\n') {
+            range.end - 1
+        } else {
+            range.end
+        }
+    }
+}
+
+fn style_at(spans: &[TextSpan], index: usize) -> TextStyle {
+    spans
+        .iter()
+        .rev()
+        .find(|span| span.range.contains(&index))
+        .map_or_else(TextStyle::default, |span| span.style)
+}
+
+fn round(x: f32) -> i32 {
+    if x < 0.0 {
+        (x - 0.5) as i32
+    } else {
+        (x + 0.5) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A monospaced font, ten pixels per character.
+    struct Mono;
+
+    impl FontMetrics for Mono {
+        fn ascent(&self) -> f32 {
+            8.0
+        }
+
+        fn descent(&self) -> f32 {
+            2.0
+        }
+
+        fn advance(&self, _c: char) -> f32 {
+            10.0
+        }
+    }
+
+    fn line_texts(layout: &TextLayout) -> Vec<&str> {
+        layout
+            .lines()
+            .iter()
+            .map(|line| &layout.text()[line.range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn breaks_at_newlines() {
+        let layout = TextLayout::new("ab\ncd\n", &Mono, None);
+        assert_eq!(line_texts(&layout), ["ab\n", "cd\n", ""]);
+        assert_eq!(layout.size(), (20.0, 30.0));
+    }
+
+    #[test]
+    fn wraps_at_whitespace() {
+        let layout = TextLayout::new("aa bb cc", &Mono, Some(55.0));
+        assert_eq!(line_texts(&layout), ["aa bb ", "cc"]);
+        assert_eq!(layout.lines()[0].width, 50.0);
+        assert_eq!(layout.lines()[1].baseline, 18.0);
+    }
+
+    #[test]
+    fn lets_whitespace_hang() {
+        let layout = TextLayout::new("aaaa    b", &Mono, Some(40.0));
+        assert_eq!(line_texts(&layout), ["aaaa    ", "b"]);
+        assert_eq!(layout.lines()[0].width, 40.0);
+    }
+
+    #[test]
+    fn breaks_long_words() {
+        let layout = TextLayout::new("abcdefg", &Mono, Some(30.0));
+        assert_eq!(line_texts(&layout), ["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn always_places_one_character_per_line() {
+        let layout = TextLayout::new("ab", &Mono, Some(5.0));
+        assert_eq!(line_texts(&layout), ["a", "b"]);
+    }
+
+    #[test]
+    fn places_caret_between_characters() {
+        let layout = TextLayout::new("ab\ncd", &Mono, None);
+        assert_eq!(layout.caret_rect_for_index(0), Rectangle::new(0, 0, 1, 10));
+        assert_eq!(layout.caret_rect_for_index(2), Rectangle::new(20, 0, 1, 10));
+        assert_eq!(layout.caret_rect_for_index(3), Rectangle::new(0, 10, 1, 10));
+        assert_eq!(
+            layout.caret_rect_for_index(5),
+            Rectangle::new(20, 10, 1, 10)
+        );
+
+        let layout = TextLayout::new("ab\n", &Mono, None);
+        assert_eq!(layout.caret_rect_for_index(3), Rectangle::new(0, 10, 1, 10));
+    }
+
+    #[test]
+    fn maps_points_to_positions() {
+        let layout = TextLayout::new("ab\ncd", &Mono, None);
+        assert_eq!(layout.position_at_point(4.0, 5.0), 0);
+        assert_eq!(layout.position_at_point(6.0, 5.0), 1);
+        assert_eq!(layout.position_at_point(100.0, 5.0), 2);
+        assert_eq!(layout.position_at_point(16.0, 15.0), 5);
+        assert_eq!(layout.position_at_point(-5.0, -5.0), 0);
+        assert_eq!(layout.position_at_point(0.0, 500.0), 3);
+    }
+}
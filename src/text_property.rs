@@ -0,0 +1,135 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use alloc::vec::Vec;
+
+/// The encoding of the text in a `TextProperty`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TextEncoding {
+    /// ISO 8859-1 text, as used by the X11 `STRING` type.
+    String,
+    /// ISO 2022 text, as used by the X11 `COMPOUND_TEXT` type.
+    CompoundText,
+    /// UTF-8 text, as used by the X11 `UTF8_STRING` type.
+    Utf8String,
+}
+
+/// Text encoded for storage in a window property, such as a title.
+///
+/// Modern clients read `_NET_WM_NAME`, which is always UTF-8, but older
+/// window managers and tools only read `WM_NAME`, which must be encoded
+/// as `STRING` or `COMPOUND_TEXT`. Backends should set both, using
+/// `TextProperty::utf8` for the former and `TextProperty::legacy` for
+/// the latter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextProperty {
+    /// The encoding of the text.
+    pub encoding: TextEncoding,
+    /// The encoded text.
+    pub data: Vec<u8>,
+}
+
+// escape sequences used to switch a COMPOUND_TEXT string into and out
+// of UTF-8
+const CT_BEGIN_UTF8: &[u8] = b"\x1b%G";
+const CT_END_UTF8: &[u8] = b"\x1b%@";
+
+impl TextProperty {
+    /// Encode text as UTF-8, for properties like `_NET_WM_NAME`.
+    pub fn utf8(text: &str) -> Self {
+        TextProperty {
+            encoding: TextEncoding::Utf8String,
+            data: text.as_bytes().to_vec(),
+        }
+    }
+
+    /// Encode an application ID for the `WM_CLASS` property.
+    ///
+    /// `WM_CLASS` holds an instance name and a class name. The instance
+    /// name is the last component of the ID, in lowercase, e.g.
+    /// `nautilus` for `org.gnome.Nautilus`, and the class name is the
+    /// whole ID, which is what desktop entries match with
+    /// `StartupWMClass`. Characters outside of ISO 8859-1 are replaced
+    /// with underscores.
+    pub fn wm_class(app_id: &str) -> Self {
+        let instance = app_id.rsplit('.').next().unwrap_or(app_id);
+        let mut data = Vec::with_capacity(instance.len() + app_id.len() + 2);
+        push_latin1(&mut data, instance.chars().map(|c| c.to_ascii_lowercase()));
+        push_latin1(&mut data, app_id.chars());
+
+        TextProperty {
+            encoding: TextEncoding::String,
+            data,
+        }
+    }
+
+    /// Encode text for legacy properties like `WM_NAME`.
+    ///
+    /// Text that fits in ISO 8859-1 is encoded as `STRING`. Other text
+    /// is encoded as `COMPOUND_TEXT`, with characters outside of
+    /// ISO 8859-1 wrapped in UTF-8 segments.
+    pub fn legacy(text: &str) -> Self {
+        if text.chars().all(is_string_char) {
+            return TextProperty {
+                encoding: TextEncoding::String,
+                data: text.chars().map(|c| c as u8).collect(),
+            };
+        }
+
+        let mut data = Vec::with_capacity(text.len());
+        let mut in_utf8 = false;
+        let mut buf = [0; 4];
+
+        for c in text.chars() {
+            if is_string_char(c) {
+                if in_utf8 {
+                    data.extend_from_slice(CT_END_UTF8);
+                    in_utf8 = false;
+                }
+                data.push(c as u8);
+            } else {
+                if !in_utf8 {
+                    data.extend_from_slice(CT_BEGIN_UTF8);
+                    in_utf8 = true;
+                }
+                data.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            }
+        }
+
+        if in_utf8 {
+            data.extend_from_slice(CT_END_UTF8);
+        }
+
+        TextProperty {
+            encoding: TextEncoding::CompoundText,
+            data,
+        }
+    }
+}
+
+/// Tell whether a character can be represented in a `STRING`, which
+/// allows ISO 8859-1 graphic characters plus tab and newline.
+fn is_string_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | ' '..='~' | '\u{a0}'..='\u{ff}')
+}
+
+/// Push a null-terminated ISO 8859-1 string, replacing characters that
+/// don't fit with underscores.
+fn push_latin1(data: &mut Vec<u8>, text: impl Iterator<Item = char>) {
+    data.extend(text.map(|c| if is_string_char(c) { c as u8 } else { b'_' }));
+    data.push(0);
+}
@@ -0,0 +1,176 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+use crate::Image;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// The pixel layout of a `VideoFrame`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ImageFormat {
+    /// A plane of 8-bit luma samples, followed by a plane of interleaved
+    /// U and V samples at half resolution in both directions.
+    Nv12,
+    /// Planar YUV 4:2:0, also known as I420: a plane of luma samples,
+    /// followed by U and V planes at half resolution in both directions.
+    Yuv420,
+    /// 8-bit BGRA pixels, as produced by most screen capture APIs.
+    Bgra,
+}
+
+impl ImageFormat {
+    /// Get the number of bytes a frame of the given size takes up in this
+    /// format, or `None` on overflow.
+    pub fn frame_len(self, width: u32, height: u32) -> Option<usize> {
+        let luma = (width as usize).checked_mul(height as usize)?;
+        let chroma = (width as usize)
+            .div_ceil(2)
+            .checked_mul((height as usize).div_ceil(2))?;
+
+        match self {
+            ImageFormat::Nv12 | ImageFormat::Yuv420 => luma.checked_add(chroma.checked_mul(2)?),
+            ImageFormat::Bgra => luma.checked_mul(4),
+        }
+    }
+}
+
+/// A frame of video, ready to be presented with
+/// `Runtime::window_present_frame`.
+///
+/// Backends hand YUV frames straight to the hardware where they can, so
+/// callers do not need to convert every frame to RGBA themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VideoFrame {
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl VideoFrame {
+    /// Create a new frame from its format, its dimensions and its data.
+    ///
+    /// Returns `None` if `data` is not exactly the size that
+    /// `ImageFormat::frame_len` expects.
+    pub fn new(format: ImageFormat, width: u32, height: u32, data: Vec<u8>) -> Option<Self> {
+        if format.frame_len(width, height)? == data.len() {
+            Some(VideoFrame {
+                format,
+                width,
+                height,
+                data,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the format of the frame.
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Get the width of the frame.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the frame.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the raw data of the frame.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the planes of the frame.
+    ///
+    /// This is one plane for `Bgra`, two for `Nv12` and three for
+    /// `Yuv420`.
+    pub fn planes(&self) -> Vec<&[u8]> {
+        let luma = self.width as usize * self.height as usize;
+        let chroma = (self.width as usize).div_ceil(2) * (self.height as usize).div_ceil(2);
+
+        match self.format {
+            ImageFormat::Bgra => alloc::vec![&self.data[..]],
+            ImageFormat::Nv12 => {
+                let (y, uv) = self.data.split_at(luma);
+                alloc::vec![y, uv]
+            }
+            ImageFormat::Yuv420 => {
+                let (y, rest) = self.data.split_at(luma);
+                let (u, v) = rest.split_at(chroma);
+                alloc::vec![y, u, v]
+            }
+        }
+    }
+
+    /// Convert this frame into an RGBA `Image`.
+    ///
+    /// This is the software fallback for backends that cannot present
+    /// the frame's format directly. YUV frames are taken to use BT.601
+    /// limited-range coefficients.
+    pub fn to_image(&self) -> Image {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut pixels = Vec::with_capacity(width * height * 4);
+
+        match self.format {
+            ImageFormat::Bgra => {
+                for px in self.data.chunks_exact(4) {
+                    pixels.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+                }
+            }
+            ImageFormat::Nv12 | ImageFormat::Yuv420 => {
+                let planes = self.planes();
+                let chroma_width = width.div_ceil(2);
+
+                for row in 0..height {
+                    for col in 0..width {
+                        let y = planes[0][row * width + col];
+                        let chroma = (row / 2) * chroma_width + col / 2;
+                        let (u, v) = match self.format {
+                            ImageFormat::Nv12 => (planes[1][chroma * 2], planes[1][chroma * 2 + 1]),
+                            _ => (planes[1][chroma], planes[2][chroma]),
+                        };
+
+                        let [r, g, b] = yuv_to_rgb(y, u, v);
+                        pixels.extend_from_slice(&[r, g, b, 0xFF]);
+                    }
+                }
+            }
+        }
+
+        Image::new(self.width, self.height, pixels).expect("pixel count matches dimensions")
+    }
+}
+
+/// Convert a BT.601 limited-range YUV sample to RGB.
+fn yuv_to_rgb(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let c = 298 * (i32::from(y) - 16);
+    let d = i32::from(u) - 128;
+    let e = i32::from(v) - 128;
+
+    let clamp = |x: i32| u8::try_from(((x + 128) >> 8).clamp(0, 255)).unwrap_or(0);
+
+    [
+        clamp(c + 409 * e),
+        clamp(c - 100 * d - 208 * e),
+        clamp(c + 516 * d),
+    ]
+}
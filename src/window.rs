@@ -15,10 +15,18 @@
 // Public License along with gui-tools. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use core::num::NonZeroUsize;
+use crate::{color::Rgba, Corner, Edge, Error, EventMask, Rectangle};
+use alloc::{string::String, sync::Arc};
+use core::{any::Any, convert::TryFrom, num::NonZeroUsize};
+
+/// Arbitrary data that a toolkit associates with a window.
+///
+/// This is reference counted, so that it can be handed out by the
+/// runtime without keeping the runtime borrowed.
+pub type UserData = Arc<dyn Any + Send + Sync>;
 
 /// A window that belongs to a runtime.
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Window(NonZeroUsize);
 
@@ -33,3 +41,307 @@ impl Window {
         self.0
     }
 }
+
+impl TryFrom<usize> for Window {
+    type Error = Error;
+
+    /// Convert a raw window ID, e.g. an XID or an `HWND`, into a window.
+    ///
+    /// Null IDs never belong to a runtime, so they are rejected with an
+    /// `ErrorKind::NotOurWindow` error rather than causing a panic.
+    fn try_from(id: usize) -> Result<Self, Error> {
+        NonZeroUsize::new(id)
+            .map(Window)
+            .ok_or_else(Error::null_window)
+    }
+}
+
+/// Whether or not a window is displayed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Visibility {
+    /// The window is displayed.
+    Visible,
+    /// The window is not displayed.
+    Hidden,
+    /// The window is minimized, e.g. to the taskbar or to an icon.
+    Iconified,
+}
+
+/// The state of a window, as set by the user or the window manager.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WindowState {
+    /// The window is neither maximized nor fullscreen.
+    Normal,
+    /// The window is maximized.
+    Maximized,
+    /// The window covers the entire screen.
+    Fullscreen,
+}
+
+/// How the corners of a top-level window should be rounded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CornerPreference {
+    /// Square corners.
+    Square,
+    /// Slightly rounded corners.
+    RoundSmall,
+    /// Rounded corners.
+    Round,
+}
+
+/// How urgently a window asks for the user's attention.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AttentionType {
+    /// Something happened that the user may want to know about, such as
+    /// a finished download.
+    ///
+    /// The taskbar entry is flashed briefly.
+    Informational,
+    /// Something happened that needs the user's attention.
+    ///
+    /// The taskbar entry is flashed until the window is focused.
+    Critical,
+}
+
+/// How the runtime fills a window's background before it is painted.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Background {
+    /// Leave the background alone.
+    ///
+    /// This avoids flicker and a wasted fill for applications that paint
+    /// their entire window on every expose. On Windows, this suppresses
+    /// `WM_ERASEBKGND`; on X11, it sets the background pixmap to `None`.
+    None,
+    /// Fill the background with a solid color.
+    Solid(Rgba),
+    /// Fill the background with the platform's default window color.
+    #[default]
+    Themed,
+}
+
+/// Which visual, and so which pixel format, a window is created with.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VisualPreference {
+    /// Use the parent's visual, or the screen's default visual for
+    /// top-level windows.
+    #[default]
+    Default,
+    /// Use a 32-bit visual with an alpha channel, so that the window can
+    /// be translucent.
+    ///
+    /// See `Capabilities::transparency`.
+    Rgba32,
+    /// Use a visual with the given depth.
+    Depth(u8),
+}
+
+/// A visual offered by the windowing system, e.g. an X11 visual.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VisualInfo {
+    /// The ID of the visual.
+    pub id: u32,
+    /// The number of bits per pixel.
+    pub depth: u8,
+    /// Whether or not the visual has an alpha channel.
+    pub alpha: bool,
+}
+
+impl VisualPreference {
+    /// Pick the visual to create a window with, given its parent's
+    /// visual and the visuals the screen offers.
+    ///
+    /// Returns `None` if no visual matches. Backends that pick a visual
+    /// other than the parent's also need to create a matching colormap.
+    pub fn choose(self, parent: VisualInfo, visuals: &[VisualInfo]) -> Option<VisualInfo> {
+        match self {
+            VisualPreference::Default => Some(parent),
+            VisualPreference::Rgba32 => visuals
+                .iter()
+                .find(|visual| visual.depth == 32 && visual.alpha)
+                .copied(),
+            VisualPreference::Depth(depth) if depth == parent.depth => Some(parent),
+            VisualPreference::Depth(depth) => {
+                visuals.iter().find(|visual| visual.depth == depth).copied()
+            }
+        }
+    }
+}
+
+/// The edge or corner of a window that an interactive resize drags.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ResizeEdge {
+    /// The left edge.
+    Left,
+    /// The top edge.
+    Top,
+    /// The right edge.
+    Right,
+    /// The bottom edge.
+    Bottom,
+    /// The top-left corner.
+    TopLeft,
+    /// The top-right corner.
+    TopRight,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+impl From<Edge> for ResizeEdge {
+    fn from(edge: Edge) -> Self {
+        match edge {
+            Edge::Left => ResizeEdge::Left,
+            Edge::Top => ResizeEdge::Top,
+            Edge::Right => ResizeEdge::Right,
+            Edge::Bottom => ResizeEdge::Bottom,
+        }
+    }
+}
+
+impl From<Corner> for ResizeEdge {
+    fn from(corner: Corner) -> Self {
+        match corner {
+            Corner::TopLeft => ResizeEdge::TopLeft,
+            Corner::TopRight => ResizeEdge::TopRight,
+            Corner::BottomLeft => ResizeEdge::BottomLeft,
+            Corner::BottomRight => ResizeEdge::BottomRight,
+        }
+    }
+}
+
+/// The attributes of a window, queried all at once.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct WindowInfo {
+    /// The position and size of the window, relative to its parent.
+    pub geometry: Rectangle,
+    /// Whether or not the window is displayed.
+    pub visibility: Visibility,
+    /// The parent of this window, or `None` if it is a top-level window.
+    pub parent: Option<Window>,
+    /// The state of the window.
+    pub state: WindowState,
+}
+
+/// The properties used to create a window.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct WindowProps {
+    /// The title of the window.
+    pub title: String,
+    /// The initial position and size of the window, relative to its
+    /// parent.
+    pub geometry: Rectangle,
+    /// The parent of the window, or `None` for a top-level window.
+    pub parent: Option<Window>,
+    /// Whether or not the window is initially displayed.
+    pub visibility: Visibility,
+    /// How the window's background is filled before it is painted.
+    pub background: Background,
+    /// Which visual the window is created with.
+    ///
+    /// Creating the window fails if no visual matches.
+    pub visual_preference: VisualPreference,
+    /// The categories of events the window receives.
+    ///
+    /// This can be changed later with `Runtime::window_set_event_mask`.
+    pub event_mask: EventMask,
+    /// Whether or not the window should cast a drop shadow, or `None` to
+    /// use the platform's default.
+    ///
+    /// This is a hint: it uses the compositor's shadow hints, such as
+    /// `_KDE_NET_WM_SHADOW`, on X11 and DWM on Windows, and is ignored
+    /// where neither is available.
+    pub shadow: Option<bool>,
+    /// How the window's corners should be rounded, or `None` to use the
+    /// platform's default.
+    ///
+    /// This is a hint: it uses `DWMWA_WINDOW_CORNER_PREFERENCE` on
+    /// Windows 11, and is ignored where it is not supported.
+    pub corners: Option<CornerPreference>,
+    /// Whether or not the window is left out of the taskbar, e.g. for
+    /// utility or background windows.
+    pub skip_taskbar: bool,
+    /// The ID that desktop environments use to group the window with
+    /// the rest of the application, and to find its icon, e.g.
+    /// `org.example.Editor`, or `None` to use the runtime's.
+    ///
+    /// This sets `WM_CLASS` on X11 (see `TextProperty::wm_class`) and
+    /// the window's `AppUserModelID` on Windows. See also
+    /// `Runtime::set_app_id`.
+    pub app_id: Option<String>,
+    /// The startup notification ID passed to this process by the
+    /// launcher, if any.
+    ///
+    /// On X11, this is usually read from the `DESKTOP_STARTUP_ID`
+    /// environment variable. The backend uses it to complete the
+    /// launcher's startup sequence when the window is first shown.
+    pub startup_id: Option<String>,
+}
+
+impl Default for WindowProps {
+    fn default() -> Self {
+        WindowProps {
+            title: String::new(),
+            geometry: Rectangle::new(0, 0, 640, 480),
+            parent: None,
+            visibility: Visibility::Visible,
+            background: Background::default(),
+            visual_preference: VisualPreference::default(),
+            event_mask: EventMask::default(),
+            shadow: None,
+            corners: None,
+            skip_taskbar: false,
+            app_id: None,
+            startup_id: None,
+        }
+    }
+}
+
+impl WindowProps {
+    /// Create a new `WindowProps` with the default properties.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// A set of changes to apply to a window at once.
+///
+/// Fields that are `None` are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowConfig {
+    /// The new position of the window, relative to its parent.
+    pub position: Option<(i32, i32)>,
+    /// The new size of the window.
+    pub size: Option<(u32, u32)>,
+    /// The new width of the window's border.
+    pub border_width: Option<u32>,
+    /// The new visibility of the window.
+    pub visibility: Option<Visibility>,
+    /// The new title of the window.
+    pub title: Option<String>,
+}
+
+impl WindowConfig {
+    /// Create a new `WindowConfig` that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tell whether this configuration changes nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Merge a later configuration into this one, with the later
+    /// configuration's changes taking precedence.
+    pub fn merge(&mut self, later: WindowConfig) {
+        self.position = later.position.or(self.position);
+        self.size = later.size.or(self.size);
+        self.border_width = later.border_width.or(self.border_width);
+        self.visibility = later.visibility.or(self.visibility);
+        if later.title.is_some() {
+            self.title = later.title;
+        }
+    }
+}
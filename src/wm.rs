@@ -0,0 +1,72 @@
+// This file is part of gui-tools.
+//
+// gui-tools is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// gui-tools is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with gui-tools. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use alloc::string::String;
+
+/// Information about the window manager running on the display.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WmInfo {
+    /// The name of the window manager, if one is running and it
+    /// advertises its name.
+    pub name: Option<String>,
+    /// Whether or not the window manager supports the Extended Window
+    /// Manager Hints.
+    pub ewmh_support: bool,
+}
+
+/// Behaviors of a window manager that backends need to work around.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WmQuirks {
+    /// Configure requests sent before the window is mapped are
+    /// ignored, so they must be re-sent after mapping.
+    pub configure_after_map: bool,
+    /// The window manager places and sizes windows itself (e.g. a
+    /// tiling window manager), so geometry requests are only advisory.
+    pub ignores_geometry: bool,
+    /// No window manager is running, so state hints such as
+    /// fullscreen or maximized have no effect unless applied manually.
+    pub unmanaged: bool,
+}
+
+impl WmInfo {
+    /// Get the quirks known for this window manager.
+    pub fn quirks(&self) -> WmQuirks {
+        let name = match self.name {
+            Some(ref name) => name.as_str(),
+            None => {
+                return WmQuirks {
+                    unmanaged: !self.ewmh_support,
+                    ..WmQuirks::default()
+                }
+            }
+        };
+
+        // quirk table, keyed by the name advertised in
+        // _NET_SUPPORTING_WM_CHECK
+        match name {
+            "i3" | "bspwm" | "dwm" | "xmonad" | "awesome" => WmQuirks {
+                ignores_geometry: true,
+                ..WmQuirks::default()
+            },
+            "KWin" | "Mutter" | "GNOME Shell" | "Metacity" => WmQuirks {
+                configure_after_map: true,
+                ..WmQuirks::default()
+            },
+            _ => WmQuirks::default(),
+        }
+    }
+}